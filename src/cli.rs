@@ -0,0 +1,199 @@
+use ray::output::{ImageFormat, BitDepth};
+use ray::ray::color::ToneMapper;
+use std::path::PathBuf;
+
+pub const USAGE: &str = "\
+Usage: ray [OPTIONS] [SCENE]
+
+Renders SCENE (a scene file, or the name of a built-in scene) and writes the
+image to stdout or to the file given with --output.
+
+Options:
+  -o, --output <PATH>      write the image to PATH instead of stdout
+  -f, --format <FORMAT>    image format (ppm, png, hdr, pfm, exr); guessed from the output
+                           extension by default
+  -b, --bit-depth <BITS>   bits per channel of ppm and png images, 8 or 16 (default 8)
+      --exposure <STOPS>   exposure adjustment applied before tone mapping
+      --tonemap <NAME>     tone mapper for ppm, png and the preview (clamp, reinhard, aces)
+  -W, --width <PIXELS>     image width
+  -H, --height <PIXELS>    image height (keeps the scene aspect ratio when omitted)
+  -s, --spp <N>            samples per pixel
+  -d, --max-depth <N>      maximum number of bounces per path
+  -t, --threads <N>        worker threads (0 = one per core)
+      --seed <N>           random seed, for reproducible images
+      --scene <NAME|PATH>  scene to render (built-in: default)
+      --no-window          do not open the preview window
+  -h, --help               print this help
+";
+
+#[derive(Debug, Default)]
+pub struct Options {
+    pub output: Option<PathBuf>,
+    pub format: Option<ImageFormat>,
+    pub bit_depth: Option<BitDepth>,
+    pub exposure: Option<f32>,
+    pub tone_mapper: Option<ToneMapper>,
+    pub width: Option<i32>,
+    pub height: Option<i32>,
+    pub samples: Option<i32>,
+    pub max_depth: Option<i32>,
+    pub threads: Option<usize>,
+    pub seed: Option<u64>,
+    pub scene: Option<String>,
+    pub no_window: bool,
+}
+
+pub enum Command {
+    Render(Options),
+    Help,
+}
+
+impl Options {
+    // Format demandé explicitement, sinon déduit de l'extension du fichier de sortie
+    pub fn output_format(&self) -> Result<ImageFormat, String> {
+        if let Some(format) = self.format {
+            return Ok(format);
+        }
+        let Some(path) = &self.output else {
+            return Ok(ImageFormat::Ppm);
+        };
+        ImageFormat::from_path(path)
+            .ok_or_else(|| format!("cannot guess the image format of '{}', use --format", path.display()))
+    }
+}
+
+pub fn parse_args(args: impl IntoIterator<Item = String>) -> Result<Command, String> {
+    let mut options = Options::default();
+    let mut args = args.into_iter();
+
+    while let Some(arg) = args.next() {
+        // Accepte `--option valeur` comme `--option=valeur`
+        let (flag, inline_value) = match arg.split_once('=') {
+            Some((flag, value)) if flag.starts_with("--") => (flag.to_string(), Some(value.to_string())),
+            _ => (arg.clone(), None),
+        };
+        let mut value = |name: &str| -> Result<String, String> {
+            inline_value.clone()
+                .or_else(|| args.next())
+                .ok_or_else(|| format!("missing value for {name}"))
+        };
+
+        match flag.as_str() {
+            "-h" | "--help" => return Ok(Command::Help),
+            "-o" | "--output" => options.output = Some(PathBuf::from(value(&flag)?)),
+            "-f" | "--format" => {
+                let name = value(&flag)?;
+                options.format = Some(ImageFormat::from_name(&name)
+                    .ok_or_else(|| format!("unknown image format '{name}'"))?);
+            }
+            "-b" | "--bit-depth" => {
+                let bits = value(&flag)?;
+                options.bit_depth = Some(number(&flag, &bits).ok().and_then(BitDepth::from_bits)
+                    .ok_or_else(|| format!("{flag} must be 8 or 16, found '{bits}'"))?);
+            }
+            "--exposure" => options.exposure = Some(number(&flag, &value(&flag)?)?),
+            "--tonemap" => {
+                let name = value(&flag)?;
+                options.tone_mapper = Some(ToneMapper::from_name(&name)
+                    .ok_or_else(|| format!("unknown tone mapper '{name}'"))?);
+            }
+            "-W" | "--width" => options.width = Some(positive(&flag, &value(&flag)?)?),
+            "-H" | "--height" => options.height = Some(positive(&flag, &value(&flag)?)?),
+            "-s" | "--spp" => options.samples = Some(positive(&flag, &value(&flag)?)?),
+            "-d" | "--max-depth" => options.max_depth = Some(positive(&flag, &value(&flag)?)?),
+            "-t" | "--threads" => options.threads = Some(number(&flag, &value(&flag)?)?),
+            "--seed" => options.seed = Some(number(&flag, &value(&flag)?)?),
+            "--scene" => set_scene(&mut options, value(&flag)?)?,
+            "--no-window" => options.no_window = true,
+            _ if flag.starts_with('-') => return Err(format!("unknown option '{flag}'")),
+            _ => set_scene(&mut options, arg)?,
+        }
+    }
+
+    Ok(Command::Render(options))
+}
+
+fn set_scene(options: &mut Options, scene: String) -> Result<(), String> {
+    if options.scene.is_some() {
+        return Err("only one scene can be rendered at a time".to_string());
+    }
+    options.scene = Some(scene);
+    Ok(())
+}
+
+fn number<T: std::str::FromStr>(flag: &str, value: &str) -> Result<T, String> {
+    value.parse().map_err(|_| format!("invalid value '{value}' for {flag}"))
+}
+
+fn positive(flag: &str, value: &str) -> Result<i32, String> {
+    match number::<i32>(flag, value)? {
+        n if n > 0 => Ok(n),
+        _ => Err(format!("{flag} must be greater than zero")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Command, String> {
+        parse_args(args.iter().map(|arg| arg.to_string()))
+    }
+
+    fn options(args: &[&str]) -> Options {
+        match parse(args) {
+            Ok(Command::Render(options)) => options,
+            Ok(Command::Help) => panic!("unexpected help for {args:?}"),
+            Err(e) => panic!("unexpected error for {args:?}: {e}"),
+        }
+    }
+
+    fn error(args: &[&str]) -> String {
+        match parse(args) {
+            Err(e) => e,
+            Ok(_) => panic!("expected an error for {args:?}"),
+        }
+    }
+
+    #[test]
+    fn short_and_long_flags() {
+        let options = options(&["-W", "320", "--height", "200", "-s", "16", "--max-depth", "8", "-t", "0", "--seed", "7", "scene.txt"]);
+        assert_eq!(options.width, Some(320));
+        assert_eq!(options.height, Some(200));
+        assert_eq!(options.samples, Some(16));
+        assert_eq!(options.max_depth, Some(8));
+        assert_eq!(options.threads, Some(0));
+        assert_eq!(options.seed, Some(7));
+        assert_eq!(options.scene.as_deref(), Some("scene.txt"));
+        assert!(!options.no_window);
+    }
+
+    #[test]
+    fn inline_values() {
+        let options = options(&["--width=640", "--output=a=b.ppm", "--scene=default", "--no-window"]);
+        assert_eq!(options.width, Some(640));
+        // Seul le premier `=` sépare l'option de sa valeur
+        assert_eq!(options.output, Some(PathBuf::from("a=b.ppm")));
+        assert_eq!(options.scene.as_deref(), Some("default"));
+        assert!(options.no_window);
+        // Les options courtes ne prennent pas de valeur après `=`
+        assert_eq!(error(&["-W=3"]), "unknown option '-W=3'");
+    }
+
+    #[test]
+    fn help() {
+        assert!(matches!(parse(&["--width", "10", "-h"]), Ok(Command::Help)));
+        assert!(matches!(parse(&["--help"]), Ok(Command::Help)));
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(error(&["--width"]), "missing value for --width");
+        assert_eq!(error(&["--spp", "many"]), "invalid value 'many' for --spp");
+        assert_eq!(error(&["-H", "0"]), "-H must be greater than zero");
+        assert_eq!(error(&["--threads=-1"]), "invalid value '-1' for --threads");
+        assert_eq!(error(&["--fast"]), "unknown option '--fast'");
+        assert_eq!(error(&["a.txt", "b.txt"]), "only one scene can be rendered at a time");
+        assert_eq!(error(&["--scene", "a.txt", "b.txt"]), "only one scene can be rendered at a time");
+    }
+}
@@ -1,3 +1,5 @@
 pub mod ray;
 pub mod object;
-pub mod utils;
\ No newline at end of file
+pub mod utils;
+pub mod loader;
+pub mod output;
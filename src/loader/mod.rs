@@ -0,0 +1,71 @@
+pub mod obj;
+pub mod mtl;
+pub mod scene;
+
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+
+// Erreur de chargement d'un fichier texte, avec le numéro de ligne fautive
+#[derive(Debug)]
+pub enum LoadError {
+    Io { path: PathBuf, source: io::Error },
+    Parse { file: String, line: usize, message: String },
+}
+
+impl LoadError {
+    pub fn io(path: &Path, source: io::Error) -> Self {
+        LoadError::Io { path: path.to_path_buf(), source }
+    }
+
+    pub fn parse(file: &str, line: usize, message: impl Into<String>) -> Self {
+        LoadError::Parse { file: file.to_string(), line, message: message.into() }
+    }
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LoadError::Io { path, source } => write!(f, "{}: {}", path.display(), source),
+            LoadError::Parse { file, line, message } => write!(f, "{}:{}: {}", file, line, message),
+        }
+    }
+}
+
+impl std::error::Error for LoadError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            LoadError::Io { source, .. } => Some(source),
+            LoadError::Parse { .. } => None,
+        }
+    }
+}
+
+// Lignes utiles d'un fichier : numéro (à partir de 1), mot-clé et arguments,
+// sans les commentaires ni les lignes vides
+pub(crate) fn statements(source: &str) -> impl Iterator<Item = (usize, &str, Vec<&str>)> {
+    source.lines().enumerate().filter_map(|(index, line)| {
+        let line = line.split('#').next().unwrap_or("");
+        let mut words = line.split_whitespace();
+        let keyword = words.next()?;
+        Some((index + 1, keyword, words.collect()))
+    })
+}
+
+pub(crate) fn parse_floats<const N: usize>(args: &[&str], file: &str, line: usize, keyword: &str) -> Result<[f32; N], LoadError> {
+    if args.len() < N {
+        return Err(LoadError::parse(file, line, format!("'{keyword}' expects {N} numbers, found {}", args.len())));
+    }
+    let mut values = [0.0; N];
+    for (value, arg) in values.iter_mut().zip(args) {
+        *value = arg.parse::<f32>()
+            .ok()
+            .filter(|v| v.is_finite())
+            .ok_or_else(|| LoadError::parse(file, line, format!("invalid number '{arg}' in '{keyword}'")))?;
+    }
+    Ok(values)
+}
+
+pub(crate) fn display_name(path: &Path) -> String {
+    path.display().to_string()
+}
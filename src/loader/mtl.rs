@@ -0,0 +1,202 @@
+use crate::ray::vec3::Vec3;
+use crate::object::material::{Material, Lambertian, Metal, Dielectric, DiffuseLight};
+use crate::object::texture::{Texture, SolidColor, ImageTexture};
+use crate::loader::{LoadError, statements, parse_floats, display_name};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+
+pub type MaterialLibrary = HashMap<String, Arc<dyn Material>>;
+
+// Paramètres d'un matériau tels qu'écrits dans le fichier MTL
+struct MtlEntry {
+    name: String,
+    kd: Vec3,
+    ks: Vec3,
+    ke: Vec3,
+    tf: Vec3,
+    ns: f32,
+    ni: f32,
+    dissolve: f32,
+    illum: u32,
+    map_kd: Option<Arc<dyn Texture>>,
+}
+
+impl MtlEntry {
+    fn new(name: &str) -> Self {
+        MtlEntry {
+            name: name.to_string(),
+            kd: Vec3::new(0.8, 0.8, 0.8),
+            ks: Vec3::new(0.0, 0.0, 0.0),
+            ke: Vec3::new(0.0, 0.0, 0.0),
+            tf: Vec3::new(1.0, 1.0, 1.0),
+            ns: 0.0,
+            ni: 1.5,
+            dissolve: 1.0,
+            illum: 2,
+            map_kd: None,
+        }
+    }
+
+    // Traduction vers le matériau le plus proche parmi ceux du moteur
+    fn to_material(&self) -> Arc<dyn Material> {
+        let max = |c: Vec3| c.x().max(c.y()).max(c.z());
+
+        if max(self.ke) > 0.0 {
+            return Arc::new(DiffuseLight { emit: self.ke });
+        }
+        if self.dissolve < 1.0 || matches!(self.illum, 4 | 6 | 7 | 9) {
+            return Arc::new(Dielectric::new(self.tf, self.ni, 0.0));
+        }
+        if matches!(self.illum, 3 | 5 | 8) || max(self.ks) > max(self.kd) {
+            // Exposant de Phong converti en rugosité approximative
+            let fuzz = (2.0 / (self.ns + 2.0)).sqrt();
+            return Arc::new(Metal::new(self.ks, fuzz));
+        }
+        let albedo = self.map_kd.clone().unwrap_or_else(|| Arc::new(SolidColor::new(self.kd)));
+        Arc::new(Lambertian::from_texture(albedo, 1.0))
+    }
+}
+
+pub fn load_mtl(path: impl AsRef<Path>, warnings: &mut Vec<LoadError>) -> Result<MaterialLibrary, LoadError> {
+    let path = path.as_ref();
+    let source = fs::read_to_string(path).map_err(|e| LoadError::io(path, e))?;
+    let base_dir = path.parent().unwrap_or(Path::new(""));
+    parse_mtl(&source, &display_name(path), base_dir, warnings)
+}
+
+// `base_dir` sert à résoudre les chemins des textures ; les problèmes qui
+// n'empêchent pas le chargement sont ajoutés à `warnings`
+pub fn parse_mtl(source: &str, file: &str, base_dir: &Path, warnings: &mut Vec<LoadError>) -> Result<MaterialLibrary, LoadError> {
+    let mut entries: Vec<MtlEntry> = Vec::new();
+
+    for (line, keyword, args) in statements(source) {
+        if keyword == "newmtl" {
+            let name = args.join(" ");
+            if name.is_empty() {
+                return Err(LoadError::parse(file, line, "'newmtl' needs a material name"));
+            }
+            entries.push(MtlEntry::new(&name));
+            continue;
+        }
+
+        let Some(entry) = entries.last_mut() else {
+            return Err(LoadError::parse(file, line, format!("'{keyword}' appears before any 'newmtl'")));
+        };
+        match keyword {
+            "Kd" => entry.kd = color(&args, file, line, keyword)?,
+            "Ks" => entry.ks = color(&args, file, line, keyword)?,
+            "Ke" => entry.ke = color(&args, file, line, keyword)?,
+            "Tf" => entry.tf = color(&args, file, line, keyword)?,
+            "Ns" => entry.ns = parse_floats::<1>(&args, file, line, keyword)?[0],
+            "Ni" => entry.ni = parse_floats::<1>(&args, file, line, keyword)?[0],
+            "d" => entry.dissolve = parse_floats::<1>(&args, file, line, keyword)?[0],
+            "Tr" => entry.dissolve = 1.0 - parse_floats::<1>(&args, file, line, keyword)?[0],
+            "illum" => {
+                entry.illum = args.first()
+                    .and_then(|arg| arg.parse().ok())
+                    .ok_or_else(|| LoadError::parse(file, line, "'illum' expects an integer"))?;
+            }
+            "map_Kd" => {
+                // Les options (-s, -o...) précèdent le nom du fichier, toujours en dernier
+                let Some(texture_file) = args.last() else {
+                    return Err(LoadError::parse(file, line, "'map_Kd' needs a file name"));
+                };
+                // Seul le PPM est lu : une image illisible (png, jpg...) laisse la couleur Kd
+                let texture_path = base_dir.join(texture_file);
+                match ImageTexture::load(&texture_path) {
+                    Ok(texture) => entry.map_kd = Some(Arc::new(texture)),
+                    Err(e) => warnings.push(LoadError::parse(file, line,
+                        format!("cannot load texture {}, using Kd: {}", texture_path.display(), e))),
+                }
+            }
+            // Autres paramètres (Ka, map_Bump, ...) sans équivalent dans le moteur
+            _ => {}
+        }
+    }
+
+    Ok(entries.iter().map(|entry| (entry.name.clone(), entry.to_material())).collect())
+}
+
+fn color(args: &[&str], file: &str, line: usize, keyword: &str) -> Result<Vec3, LoadError> {
+    // Une seule valeur signifie un gris
+    if args.len() == 1 {
+        let [v] = parse_floats::<1>(args, file, line, keyword)?;
+        return Ok(Vec3::new(v, v, v));
+    }
+    let [r, g, b] = parse_floats::<3>(args, file, line, keyword)?;
+    Ok(Vec3::new(r, g, b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ray::ray::Ray;
+    use crate::ray::hittable::HitRecord;
+
+    fn parse(source: &str) -> Result<MaterialLibrary, LoadError> {
+        parse_mtl(source, "test.mtl", Path::new(""), &mut Vec::new())
+    }
+
+    fn attenuation(material: &Arc<dyn Material>) -> Vec3 {
+        let ray = Ray::new(Vec3::new(0.0, 0.0, 1.0), Vec3::new(0.0, 0.0, -1.0));
+        let mut rec = HitRecord::new();
+        rec.normal = Vec3::new(0.0, 0.0, 1.0);
+        rec.front_face = true;
+        let mut attenuation = Vec3::new(0.0, 0.0, 0.0);
+        let mut scattered = ray;
+        material.scatter(&ray, &rec, &mut attenuation, &mut scattered);
+        attenuation
+    }
+
+    fn assert_color(color: Vec3, expected: [f32; 3]) {
+        assert!((color - Vec3::new(expected[0], expected[1], expected[2])).length() < 1e-6, "{color:?} != {expected:?}");
+    }
+
+    #[test]
+    fn material_kinds() {
+        let library = parse("\
+newmtl matte
+Ka 1 1 1
+Kd 0.2 0.4 0.6
+
+newmtl lamp
+Ke 4
+
+newmtl mirror
+Kd 0.1 0.1 0.1
+Ks 0.9 0.8 0.7
+Ns 100
+illum 3
+").unwrap();
+        assert_eq!(library.len(), 3);
+        assert_color(attenuation(&library["matte"]), [0.2, 0.4, 0.6]);
+        let ray = Ray::new(Vec3::new(0.0, 0.0, 1.0), Vec3::new(0.0, 0.0, -1.0));
+        // Une seule valeur de Ke donne un gris
+        assert_color(library["lamp"].emitted(&ray, &HitRecord::new()), [4.0, 4.0, 4.0]);
+        assert_color(library["mirror"].emitted(&ray, &HitRecord::new()), [0.0, 0.0, 0.0]);
+        assert_color(attenuation(&library["mirror"]), [0.9, 0.8, 0.7]);
+    }
+
+    #[test]
+    fn unreadable_texture_keeps_kd() {
+        let mut warnings = Vec::new();
+        let library = parse_mtl("newmtl a\nKd 0.1 0.2 0.3\nmap_Kd -s 2 2 2 missing.png\n", "test.mtl", Path::new(""), &mut warnings).unwrap();
+        assert_color(attenuation(&library["a"]), [0.1, 0.2, 0.3]);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].to_string().starts_with("test.mtl:3: cannot load texture missing.png, using Kd: "), "{}", warnings[0]);
+    }
+
+    #[test]
+    fn errors() {
+        let error = |source: &str| match parse(source) {
+            Err(e) => e.to_string(),
+            Ok(_) => panic!("expected an error for {source:?}"),
+        };
+        assert_eq!(error("# commentaire\nKd 1 1 1\n"), "test.mtl:2: 'Kd' appears before any 'newmtl'");
+        assert_eq!(error("newmtl\n"), "test.mtl:1: 'newmtl' needs a material name");
+        assert_eq!(error("newmtl a\nillum high\n"), "test.mtl:2: 'illum' expects an integer");
+        assert_eq!(error("newmtl a\nKd 1 x 1\n"), "test.mtl:2: invalid number 'x' in 'Kd'");
+    }
+}
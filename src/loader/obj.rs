@@ -0,0 +1,292 @@
+use crate::ray::vec3::{Vec3, Point3};
+use crate::object::material::Material;
+use crate::object::mesh::{MeshData, TriangleMesh};
+use crate::object::hittable_list::HittableList;
+use crate::loader::{LoadError, statements, parse_floats, display_name};
+use crate::loader::mtl::{MaterialLibrary, load_mtl};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+
+// Groupe (`g` ou `o`) d'un fichier OBJ, converti en maillage
+pub struct ObjGroup {
+    pub name: String,
+    pub mesh: TriangleMesh,
+}
+
+pub struct ObjModel {
+    pub groups: Vec<ObjGroup>,
+    pub warnings: Vec<LoadError>, // problèmes non bloquants (texture illisible...)
+}
+
+impl ObjModel {
+    pub fn triangle_count(&self) -> usize {
+        self.groups.iter().map(|group| group.mesh.triangle_count()).sum()
+    }
+
+    pub fn add_to(self, world: &mut HittableList) {
+        for group in self.groups {
+            world.add(Box::new(group.mesh));
+        }
+    }
+}
+
+// Indices (position, texture, normale) d'un sommet de face, à partir de 0
+type FaceVertex = (usize, Option<usize>, Option<usize>);
+
+// Faces accumulées pour un groupe avant la construction du maillage
+struct GroupBuilder {
+    name: String,
+    data: MeshData,
+    vertex_map: HashMap<FaceVertex, u32>,
+    materials: Vec<Arc<dyn Material>>,
+    material_names: HashMap<String, u32>,
+    missing_normals: bool,
+    missing_uvs: bool,
+}
+
+impl GroupBuilder {
+    fn new(name: &str) -> Self {
+        GroupBuilder {
+            name: name.to_string(),
+            data: MeshData::default(),
+            vertex_map: HashMap::new(),
+            materials: Vec::new(),
+            material_names: HashMap::new(),
+            missing_normals: false,
+            missing_uvs: false,
+        }
+    }
+
+    fn material_index(&mut self, name: &str, material: &Arc<dyn Material>) -> u32 {
+        if let Some(&index) = self.material_names.get(name) {
+            return index;
+        }
+        let index = self.materials.len() as u32;
+        self.materials.push(material.clone());
+        self.material_names.insert(name.to_string(), index);
+        index
+    }
+
+    fn vertex(&mut self, key: FaceVertex, positions: &[Point3], uvs: &[(f32, f32)], normals: &[Vec3]) -> u32 {
+        if let Some(&index) = self.vertex_map.get(&key) {
+            return index;
+        }
+        let index = self.data.positions.len() as u32;
+        self.data.positions.push(positions[key.0]);
+        match key.1 {
+            Some(t) => self.data.uvs.push(uvs[t]),
+            None => {
+                self.data.uvs.push((0.0, 0.0));
+                self.missing_uvs = true;
+            }
+        }
+        match key.2 {
+            // Une normale nulle reste nulle : le maillage utilise alors celle de la face
+            Some(n) if !normals[n].near_zero() => self.data.normals.push(normals[n].unit_vector()),
+            Some(_) => self.data.normals.push(Vec3::new(0.0, 0.0, 0.0)),
+            None => {
+                self.data.normals.push(Vec3::new(0.0, 0.0, 0.0));
+                self.missing_normals = true;
+            }
+        }
+        self.vertex_map.insert(key, index);
+        index
+    }
+
+    fn build(mut self) -> Option<ObjGroup> {
+        if self.data.triangles.is_empty() {
+            return None;
+        }
+        // Attributs partiels : on retombe sur la normale de la face et les barycentriques
+        if self.missing_normals {
+            self.data.normals.clear();
+        }
+        if self.missing_uvs {
+            self.data.uvs.clear();
+        }
+        Some(ObjGroup { name: self.name, mesh: TriangleMesh::new(self.data, self.materials) })
+    }
+}
+
+// `default_material` s'applique aux faces sans `usemtl` ou dont le matériau est introuvable
+pub fn load_obj(path: impl AsRef<Path>, default_material: Arc<dyn Material>) -> Result<ObjModel, LoadError> {
+    let path = path.as_ref();
+    let source = fs::read_to_string(path).map_err(|e| LoadError::io(path, e))?;
+    let base_dir = path.parent().unwrap_or(Path::new(""));
+    parse_obj(&source, &display_name(path), base_dir, default_material)
+}
+
+// `base_dir` sert à résoudre les chemins des bibliothèques `mtllib`
+pub fn parse_obj(source: &str, file: &str, base_dir: &Path, default_material: Arc<dyn Material>) -> Result<ObjModel, LoadError> {
+    let mut positions: Vec<Point3> = Vec::new();
+    let mut uvs: Vec<(f32, f32)> = Vec::new();
+    let mut normals: Vec<Vec3> = Vec::new();
+    let mut library = MaterialLibrary::new();
+
+    let mut groups = Vec::new();
+    let mut warnings = Vec::new();
+    let mut current = GroupBuilder::new("default");
+    let mut material_name = String::new();
+    let mut material = default_material.clone();
+    let mut face = Vec::new();
+
+    for (line, keyword, args) in statements(source) {
+        match keyword {
+            "v" => {
+                let [x, y, z] = parse_floats::<3>(&args, file, line, keyword)?;
+                positions.push(Point3::new(x, y, z));
+            }
+            "vt" => {
+                let u = parse_floats::<1>(&args, file, line, keyword)?[0];
+                let v = if args.len() > 1 { parse_floats::<2>(&args, file, line, keyword)?[1] } else { 0.0 };
+                uvs.push((u, v));
+            }
+            "vn" => {
+                let [x, y, z] = parse_floats::<3>(&args, file, line, keyword)?;
+                normals.push(Vec3::new(x, y, z));
+            }
+            "f" => {
+                if args.len() < 3 {
+                    return Err(LoadError::parse(file, line, format!("face needs at least 3 vertices, found {}", args.len())));
+                }
+                face.clear();
+                for arg in &args {
+                    let key = parse_face_vertex(arg, positions.len(), uvs.len(), normals.len())
+                        .map_err(|message| LoadError::parse(file, line, message))?;
+                    face.push(current.vertex(key, &positions, &uvs, &normals));
+                }
+                let material_index = current.material_index(&material_name, &material);
+                // Triangulation en éventail des quadrilatères et polygones
+                for k in 1..face.len() - 1 {
+                    current.data.triangles.push([face[0], face[k], face[k + 1]]);
+                    current.data.face_materials.push(material_index);
+                }
+            }
+            "g" | "o" => {
+                let name = if args.is_empty() { "default".to_string() } else { args.join(" ") };
+                let finished = std::mem::replace(&mut current, GroupBuilder::new(&name));
+                groups.extend(finished.build());
+            }
+            "usemtl" => {
+                material_name = args.join(" ");
+                material = library.get(&material_name).cloned().unwrap_or_else(|| default_material.clone());
+            }
+            "mtllib" => {
+                if args.is_empty() {
+                    return Err(LoadError::parse(file, line, "'mtllib' needs a file name"));
+                }
+                for name in &args {
+                    library.extend(load_mtl(base_dir.join(name), &mut warnings)?);
+                }
+            }
+            // Instructions valides sans équivalent dans le moteur (lissage, lignes, courbes...)
+            "s" | "l" | "p" | "vp" | "mg" | "lod" | "bevel" | "c_interp" | "d_interp"
+            | "usemap" | "maplib" | "shadow_obj" | "trace_obj" | "ctech" | "stech"
+            | "cstype" | "deg" | "bmat" | "step" | "curv" | "curv2" | "surf" | "parm"
+            | "trim" | "hole" | "scrv" | "sp" | "con" | "end" | "call" | "csh" => {}
+            _ => return Err(LoadError::parse(file, line, format!("unknown statement '{keyword}'"))),
+        }
+    }
+    groups.extend(current.build());
+
+    Ok(ObjModel { groups, warnings })
+}
+
+// Analyse `v`, `v/vt`, `v//vn` ou `v/vt/vn`, indices négatifs relatifs à la fin
+fn parse_face_vertex(arg: &str, position_count: usize, uv_count: usize, normal_count: usize) -> Result<FaceVertex, String> {
+    let mut parts = arg.split('/');
+    let position = parts.next().unwrap_or("");
+    let uv = parts.next().filter(|s| !s.is_empty());
+    let normal = parts.next().filter(|s| !s.is_empty());
+    if parts.next().is_some() {
+        return Err(format!("malformed face vertex '{arg}'"));
+    }
+
+    let resolve = |token: &str, count: usize, kind: &str| -> Result<usize, String> {
+        let index: i64 = token.parse().map_err(|_| format!("invalid {kind} index '{token}' in '{arg}'"))?;
+        let resolved = if index < 0 { count as i64 + index } else { index - 1 };
+        if index == 0 || resolved < 0 || resolved >= count as i64 {
+            return Err(format!("{kind} index {index} out of range (have {count}) in '{arg}'"));
+        }
+        Ok(resolved as usize)
+    };
+
+    Ok((
+        resolve(position, position_count, "vertex")?,
+        uv.map(|t| resolve(t, uv_count, "texture")).transpose()?,
+        normal.map(|n| resolve(n, normal_count, "normal")).transpose()?,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::object::material::Lambertian;
+
+    fn parse(source: &str) -> Result<ObjModel, LoadError> {
+        let material = Arc::new(Lambertian::new(Vec3::new(0.5, 0.5, 0.5), 1.0));
+        parse_obj(source, "test.obj", Path::new(""), material)
+    }
+
+    fn error(source: &str) -> String {
+        match parse(source) {
+            Err(e) => e.to_string(),
+            Ok(_) => panic!("expected an error for {source:?}"),
+        }
+    }
+
+    const SQUARE: &str = "v 0 0 0\nv 1 0 0\nv 1 1 0\nv 0 1 0\n";
+
+    #[test]
+    fn fan_triangulation() {
+        let model = parse(&format!("{SQUARE}v 2 0 0\nf 1 2 3 4\nf 1 2 5 3 4\n")).unwrap();
+        assert_eq!(model.groups.len(), 1);
+        assert_eq!(model.groups[0].name, "default");
+        assert_eq!(model.triangle_count(), 5);
+        assert_eq!(model.groups[0].mesh.vertex_count(), 5);
+    }
+
+    #[test]
+    fn negative_indices() {
+        // -1 désigne le dernier sommet lu jusqu'ici
+        let model = parse("v 0 0 0\nv 1 0 0\nv 0 1 0\nf 1 2 3\nf -3 -2 -1\nv 1 1 0\nf -3 -2 -1\n").unwrap();
+        assert_eq!(model.triangle_count(), 3);
+        assert_eq!(model.groups[0].mesh.vertex_count(), 4);
+    }
+
+    #[test]
+    fn vertex_formats() {
+        let source = format!("{SQUARE}vt 0 0\nvt 1 0\nvt 1 1\nvn 0 0 1\nf 1/1/1 2/2/1 3/3/1\nf 1//1 3//1 4//1\nf 1/1 2/2 3/3\n");
+        assert_eq!(parse(&source).unwrap().triangle_count(), 3);
+    }
+
+    #[test]
+    fn groups() {
+        let source = format!("{SQUARE}f 1 2 3\ng first\nf 1 3 4\ng empty\no second part\nf 1 2 3\nf 1 3 4\n");
+        let model = parse(&source).unwrap();
+        let groups: Vec<_> = model.groups.iter().map(|g| (g.name.as_str(), g.mesh.triangle_count())).collect();
+        // Les groupes sans face disparaissent
+        assert_eq!(groups, [("default", 1), ("first", 1), ("second part", 2)]);
+    }
+
+    #[test]
+    fn ignored_statements() {
+        let source = format!("# commentaire\n{SQUARE}s 1\nl 1 2\nusemtl missing\nf 1 2 3 # fin\n");
+        assert_eq!(parse(&source).unwrap().triangle_count(), 1);
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(error("v 0 0 0\nvx 1 2 3\n"), "test.obj:2: unknown statement 'vx'");
+        assert_eq!(error("v 0 0\n"), "test.obj:1: 'v' expects 3 numbers, found 2");
+        assert_eq!(error(&format!("{SQUARE}f 1 2\n")), "test.obj:5: face needs at least 3 vertices, found 2");
+        assert_eq!(error(&format!("{SQUARE}f 1 2 5\n")), "test.obj:5: vertex index 5 out of range (have 4) in '5'");
+        assert_eq!(error(&format!("{SQUARE}f 0 1 2\n")), "test.obj:5: vertex index 0 out of range (have 4) in '0'");
+        assert_eq!(error(&format!("{SQUARE}f -5 1 2\n")), "test.obj:5: vertex index -5 out of range (have 4) in '-5'");
+        assert_eq!(error(&format!("{SQUARE}f 1/1 2/1 3/1\n")), "test.obj:5: texture index 1 out of range (have 0) in '1/1'");
+        assert_eq!(error(&format!("{SQUARE}f 1/2/3/4 2 3\n")), "test.obj:5: malformed face vertex '1/2/3/4'");
+        assert_eq!(error(&format!("{SQUARE}f a 2 3\n")), "test.obj:5: invalid vertex index 'a' in 'a'");
+    }
+}
@@ -0,0 +1,838 @@
+// Format de description de scène : une instruction par ligne, `#` pour les commentaires.
+//
+//   resolution <largeur> <hauteur>
+//   samples <n>
+//   max_depth <n>
+//   camera <depuis x y z> <vers x y z> <haut x y z> <fov vertical en degrés>
+//   defocus <ouverture en degrés> [distance du plan net]
+//   shutter <ouverture> <fermeture>
+//   aperture circle | aperture polygon <lames> [rotation en degrés] | aperture mask <fichier.ppm>
+//   exposure <diaphragmes>
+//   tonemap clamp | reinhard | aces
+//   background sky | background <r g b> | background gradient <r g b bas> <r g b haut>
+//   texture <nom> solid <r g b>
+//   texture <nom> checker <échelle> <couleur paire> <couleur impaire>
+//   texture <nom> image <fichier.ppm>
+//   texture <nom> noise|turbulence|marble|wood|granite <échelle> [octaves <n>] [simplex] [seed <n>] [<couleur> <couleur>]
+//   material <nom> lambertian <couleur> [fuzz]
+//   material <nom> metal <couleur> <fuzz>
+//   material <nom> dielectric <indice> [<couleur> [fuzz]]
+//   material <nom> light <r g b>
+//   material <nom> isotropic <couleur>
+//   material <nom> henyey_greenstein <couleur> <anisotropie de -1 à 1>
+//   sphere <x y z> <rayon> <matériau>
+//   moving_sphere <centre au temps 0> <centre au temps 1> <rayon> <matériau>
+//   triangle <x y z> <x y z> <x y z> <matériau>
+//   quad <coin x y z> <arête u> <arête v> <matériau>
+//   disk <centre x y z> <normale x y z> <rayon> <matériau>
+//   plane <point x y z> <normale x y z> <matériau>
+//   box <coin x y z> <coin opposé x y z> <matériau>
+//   cylinder <base x y z> <sommet x y z> <rayon> <matériau>
+//   cone <base x y z> <sommet x y z> <rayon de base> <rayon au sommet> <matériau>
+//   torus <centre x y z> <axe x y z> <grand rayon> <rayon du tube> <matériau>
+//   sdf <nom> sphere <centre x y z> <rayon>
+//   sdf <nom> box <centre x y z> <demi-taille x y z>
+//   sdf <nom> torus <centre x y z> <grand rayon> <rayon du tube>
+//   sdf <nom> smooth_union <sdf> <sdf> <largeur du raccord>
+//   sdf <nom> repeat <sdf> <période x y z>
+//   sdf <nom> twist <sdf> <degrés par unité de hauteur>
+//   march <sdf> <matériau>
+//   pyramid <x y z> <base> <hauteur> <rotation x y z en degrés> <matériau>
+//   mesh <fichier.obj> <matériau par défaut>
+//   object <nom> <forme> <arguments de la forme>
+//   csg <nom> union|intersection|difference <objet> <objet>
+//   instance <nom> <transformation>... [move <dx dy dz>]
+//   medium <objet> <densité> <matériau>
+//   atmosphere <densité> <matériau>
+//   density <nom> grid <fichier> <coin x y z> <coin opposé x y z>
+//   density <nom> raw <fichier> <nx ny nz> <coin x y z> <coin opposé x y z>
+//   density <nom> noise <graine> <fréquence> <octaves>
+//   volume <objet> <densité> <échelle> <matériau> [emission <r g b> [<densité>]] [surface]
+//
+// Les textures de bruit sont évaluées au point touché, en 3D : `échelle` est la
+// fréquence du motif par unité de la scène. Le bruit est celui de Perlin sauf
+// avec `simplex` ; les deux couleurs remplacent celles du motif (veines du
+// marbre, cernes du bois...).
+//
+// Une forme est l'une des instructions sphere, moving_sphere, triangle, quad,
+// disk, plane, box, cylinder, cone, torus, march, pyramid ou mesh ; `object` la
+// définit sans l'ajouter à la scène, chaque `instance` la place ensuite sans
+// dupliquer sa géométrie. Les transformations s'appliquent dans l'ordre :
+// translate <x y z>, rotate_x|rotate_y|rotate_z <degrés>, rotate <axe x y z> <degrés>,
+// scale <s> | scale <x y z>, matrix <16 nombres par lignes, la dernière 0 0 0 1>.
+// Avec `move`, l'instance se déplace de <dx dy dz> entre les temps 0 et 1 (flou
+// de bougé).
+//
+// `csg` définit un nouvel objet à partir de deux objets déjà définis, qui
+// doivent être des solides fermés ; une différence retire le second du premier.
+// Le résultat s'ajoute à la scène avec `instance` et peut lui-même servir
+// d'opérande.
+//
+// Les instructions `sdf` assemblent des fonctions de distance (tore d'axe Y,
+// torsion autour de Y d'une forme bornée en x et z, période 0 pour ne pas
+// répéter un axe) ; `march` ajoute la surface correspondante, trouvée par
+// lancer de sphères.
+//
+// `medium` remplit un objet fermé d'un brouillard homogène, `atmosphere` toute la
+// scène (le fond reste net) ; leur matériau doit être isotropic ou
+// henyey_greenstein. La densité, positive, est l'inverse du libre parcours moyen.
+//
+// `volume` remplit un objet fermé d'un milieu de densité variable, suivi pas à
+// pas par la caméra : l'extinction vaut <échelle> (> 0) fois la densité, et chaque
+// collision émet <r g b> fois le champ d'émission (la densité par défaut).
+// Avec `surface`, le contour garde son matériau (verre...) au lieu d'être
+// invisible. Une grille texte donne `nx ny nz` puis les valeurs, x variant le
+// plus vite ; une grille raw contient les mêmes valeurs en flottants 32 bits
+// petit-boutistes.
+//
+// Une <couleur> est soit trois nombres, soit le nom d'une texture. Les sphères,
+// triangles, quads et disques dont le matériau est une lumière sont échantillonnés
+// directement.
+
+use crate::ray::vec3::{Vec3, Point3};
+use crate::ray::hittable::Hittable;
+use crate::ray::transform::{Matrix4, Transform};
+use crate::object::hittable_list::HittableList;
+use crate::object::bvh::Bvh;
+use crate::object::instance::Transformed;
+use crate::object::moving::Moving;
+use crate::object::csg::{Csg, CsgOp};
+use crate::object::sdf::{Sdf, SdfObject, SdfSphere, SdfBox, SdfTorus, SmoothUnion, Repeat, Twist};
+use crate::object::light::Light;
+use crate::object::material::{Material, Lambertian, Metal, Dielectric, DiffuseLight};
+use crate::object::material::{Isotropic, HenyeyGreenstein};
+use crate::object::medium::{ConstantMedium, Atmosphere};
+use crate::object::volume::{DensityField, DensityGrid, NoiseDensity, HeterogeneousMedium, MediumBoundary};
+use crate::utils::aabb::Aabb;
+use crate::utils::interval::Interval;
+use crate::utils::noise::{Noise, Perlin, Simplex};
+use crate::object::texture::{Texture, SolidColor, CheckerTexture, ImageTexture, NoiseTexture, NoisePattern};
+use crate::object::sphere::Sphere;
+use crate::object::triangle::Triangle;
+use crate::object::quad::Quad;
+use crate::object::disk::Disk;
+use crate::object::plane::Plane;
+use crate::object::cuboid::Cuboid;
+use crate::object::cylinder::Cylinder;
+use crate::object::cone::Cone;
+use crate::object::torus::Torus;
+use crate::object::pyramid::Pyramid;
+use crate::ray::color::{DisplayTransform, ToneMapper};
+use crate::utils::camera::{Camera, Background};
+use crate::utils::aperture::{Aperture, ApertureMask};
+use crate::loader::{LoadError, statements, display_name};
+use crate::loader::obj::load_obj;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+
+pub struct Scene {
+    pub camera: Camera,
+    pub world: HittableList,
+    pub display: DisplayTransform,
+    pub warnings: Vec<LoadError>, // problèmes non bloquants, à signaler à l'utilisateur
+}
+
+pub fn load_scene(path: impl AsRef<Path>) -> Result<Scene, LoadError> {
+    let path = path.as_ref();
+    let source = fs::read_to_string(path).map_err(|e| LoadError::io(path, e))?;
+    let base_dir = path.parent().unwrap_or(Path::new(""));
+    parse_scene(&source, &display_name(path), base_dir)
+}
+
+// `base_dir` sert à résoudre les chemins des textures et des maillages
+pub fn parse_scene(source: &str, file: &str, base_dir: &Path) -> Result<Scene, LoadError> {
+    let mut world = HittableList::new();
+    let mut camera = Camera::new(16.0 / 9.0, 500, 100,
+        Point3::new(0.0, 0.0, 0.0), Point3::new(0.0, 0.0, -1.0), Vec3::new(0.0, 1.0, 0.0));
+    let mut display = DisplayTransform::default();
+    let mut textures: HashMap<String, Arc<dyn Texture>> = HashMap::new();
+    let mut materials: HashMap<String, Arc<dyn Material>> = HashMap::new();
+    let mut emitters: HashSet<String> = HashSet::new();
+    let mut phases: HashSet<String> = HashSet::new(); // matériaux utilisables dans un milieu
+    let mut objects: HashMap<String, Arc<dyn Hittable>> = HashMap::new();
+    let mut sdfs: HashMap<String, Arc<dyn Sdf>> = HashMap::new();
+    let mut densities: HashMap<String, Arc<dyn DensityField>> = HashMap::new();
+    let mut warnings = Vec::new();
+
+    for (line, keyword, words) in statements(source) {
+        let mut args = Args { words, pos: 0, file, line };
+        match keyword {
+            "resolution" => {
+                let width = args.positive_int("width")?;
+                let height = args.positive_int("height")?;
+                camera.image_width = width;
+                camera.aspect_ratio = width as f32 / height as f32;
+            }
+            "samples" => camera.samples_per_pixel = args.positive_int("sample count")?,
+            "max_depth" => camera.max_depth = args.positive_int("depth")?,
+            "camera" => {
+                camera.look_from = args.vec3()?;
+                camera.look_at = args.vec3()?;
+                camera.vup = args.vec3()?;
+                camera.vfov = args.number()?;
+            }
+            "defocus" => {
+                camera.defocus_angle = args.number()?;
+                camera.focus_dist = args.optional_number()?.unwrap_or(0.0);
+            }
+            "shutter" => {
+                camera.shutter_open = args.number()?;
+                camera.shutter_close = args.number()?;
+            }
+            "aperture" => {
+                camera.aperture = match args.word("aperture shape")? {
+                    "circle" => Aperture::Circle,
+                    "polygon" => {
+                        let blades = args.positive_int("blade count")?;
+                        if blades < 3 {
+                            return Err(args.error("an aperture polygon needs at least 3 blades"));
+                        }
+                        let rotation = args.optional_number()?.unwrap_or(0.0);
+                        Aperture::polygon(blades as u32, rotation.to_radians())
+                    }
+                    "mask" => {
+                        let path = base_dir.join(args.word("mask file")?);
+                        let mask = ApertureMask::load(&path)
+                            .map_err(|e| args.error(format!("cannot load aperture mask {}: {}", path.display(), e)))?;
+                        Aperture::Mask(Arc::new(mask))
+                    }
+                    other => return Err(args.error(format!("unknown aperture shape '{other}'"))),
+                };
+            }
+            "exposure" => display.exposure = args.number()?,
+            "tonemap" => {
+                let name = args.word("tone mapper")?;
+                display.tone_mapper = ToneMapper::from_name(name)
+                    .ok_or_else(|| args.error(format!("unknown tone mapper '{name}'")))?;
+            }
+            "background" => {
+                camera.background = match args.peek() {
+                    Some("sky") => {
+                        args.pos += 1;
+                        Background::sky()
+                    }
+                    Some("gradient") => {
+                        args.pos += 1;
+                        Background::Gradient { horizon: args.vec3()?, zenith: args.vec3()? }
+                    }
+                    _ => Background::Solid(args.vec3()?),
+                };
+            }
+            "texture" => {
+                let name = args.word("texture name")?.to_string();
+                let texture: Arc<dyn Texture> = match args.word("texture type")? {
+                    "solid" => Arc::new(SolidColor::new(args.vec3()?)),
+                    "checker" => {
+                        let scale = args.number()?;
+                        let even = args.color(&textures)?;
+                        let odd = args.color(&textures)?;
+                        Arc::new(CheckerTexture::new(scale, even, odd))
+                    }
+                    "image" => {
+                        let path = base_dir.join(args.word("image file")?);
+                        let texture = ImageTexture::load(&path)
+                            .map_err(|e| args.error(format!("cannot load texture {}: {}", path.display(), e)))?;
+                        Arc::new(texture)
+                    }
+                    other => match NoisePattern::from_name(other) {
+                        Some(pattern) => Arc::new(noise_texture(&mut args, pattern, &textures)?),
+                        None => return Err(args.error(format!("unknown texture type '{other}'"))),
+                    },
+                };
+                textures.insert(name, texture);
+            }
+            "material" => {
+                let name = args.word("material name")?.to_string();
+                emitters.remove(&name);
+                phases.remove(&name);
+                let material: Arc<dyn Material> = match args.word("material type")? {
+                    "lambertian" => {
+                        let albedo = args.color(&textures)?;
+                        let fuzz = args.optional_number()?.unwrap_or(1.0);
+                        Arc::new(Lambertian::from_texture(albedo, fuzz))
+                    }
+                    "metal" => {
+                        let albedo = args.color(&textures)?;
+                        let fuzz = args.number()?;
+                        Arc::new(Metal::from_texture(albedo, fuzz))
+                    }
+                    "dielectric" => {
+                        let ir = args.number()?;
+                        let albedo = if args.peek().is_some() {
+                            args.color(&textures)?
+                        } else {
+                            Arc::new(SolidColor::new(Vec3::new(1.0, 1.0, 1.0)))
+                        };
+                        let fuzz = args.optional_number()?.unwrap_or(0.0);
+                        Arc::new(Dielectric::from_texture(albedo, ir, fuzz))
+                    }
+                    "isotropic" => {
+                        phases.insert(name.clone());
+                        Arc::new(Isotropic::from_texture(args.color(&textures)?))
+                    }
+                    "henyey_greenstein" => {
+                        phases.insert(name.clone());
+                        let albedo = args.color(&textures)?;
+                        Arc::new(HenyeyGreenstein::from_texture(albedo, args.number()?))
+                    }
+                    "light" => {
+                        emitters.insert(name.clone());
+                        Arc::new(DiffuseLight { emit: args.vec3()? })
+                    }
+                    other => return Err(args.error(format!("unknown material type '{other}'"))),
+                };
+                materials.insert(name, material);
+            }
+            keyword if SHAPES.contains(&keyword) => {
+                let shape = parse_shape(keyword, &mut args, &materials, &emitters, &sdfs, base_dir)?;
+                warnings.extend(shape.warnings);
+                for light in shape.lights {
+                    camera.lights.add(light);
+                }
+                for object in shape.objects {
+                    world.add(object);
+                }
+            }
+            "object" => {
+                let name = args.word("object name")?.to_string();
+                let keyword = args.word("shape")?;
+                if !SHAPES.contains(&keyword) {
+                    return Err(args.error(format!("unknown shape '{keyword}'")));
+                }
+                // Les lumières instanciées ne sont pas échantillonnées directement
+                let mut shape = parse_shape(keyword, &mut args, &materials, &emitters, &sdfs, base_dir)?;
+                warnings.append(&mut shape.warnings);
+                let object: Arc<dyn Hittable> = if shape.objects.len() == 1 {
+                    Arc::from(shape.objects.remove(0))
+                } else {
+                    Arc::new(Bvh::from_objects(shape.objects))
+                };
+                objects.insert(name, object);
+            }
+            "sdf" => {
+                let name = args.word("sdf name")?.to_string();
+                let operand = |args: &mut Args| -> Result<Arc<dyn Sdf>, LoadError> {
+                    let name = args.word("sdf name")?;
+                    sdfs.get(name).cloned().ok_or_else(|| args.error(format!("unknown sdf '{name}'")))
+                };
+                let sdf: Arc<dyn Sdf> = match args.word("sdf type")? {
+                    "sphere" => Arc::new(SdfSphere::new(args.vec3()?, args.number()?)),
+                    "box" => Arc::new(SdfBox::new(args.vec3()?, args.vec3()?)),
+                    "torus" => Arc::new(SdfTorus::new(args.vec3()?, args.number()?, args.number()?)),
+                    "smooth_union" => {
+                        let (a, b) = (operand(&mut args)?, operand(&mut args)?);
+                        Arc::new(SmoothUnion::new(a, b, args.number()?))
+                    }
+                    "repeat" => {
+                        let shape = operand(&mut args)?;
+                        Arc::new(Repeat::new(shape, args.vec3()?))
+                    }
+                    "twist" => {
+                        let shape = operand(&mut args)?;
+                        let bbox = shape.bounding_box();
+                        let bounded = |i: Interval| i.min.is_finite() && i.max.is_finite();
+                        if !(bounded(bbox.x) && bounded(bbox.z)) {
+                            return Err(args.error("twist needs a shape bounded in x and z"));
+                        }
+                        Arc::new(Twist::new(shape, args.number()?.to_radians()))
+                    }
+                    other => return Err(args.error(format!("unknown sdf type '{other}'"))),
+                };
+                sdfs.insert(name, sdf);
+            }
+            "medium" => {
+                let name = args.word("object name")?;
+                let boundary = objects.get(name).cloned()
+                    .ok_or_else(|| args.error(format!("unknown object '{name}'")))?;
+                let density = args.positive_number("medium density")?;
+                let phase = args.phase(&materials, &phases)?;
+                world.add(Box::new(ConstantMedium::new(Box::new(boundary), density, phase)));
+            }
+            "density" => {
+                let name = args.word("density name")?.to_string();
+                let field: Arc<dyn DensityField> = match args.word("density type")? {
+                    kind @ ("grid" | "raw") => {
+                        let path = base_dir.join(args.word("grid file")?);
+                        let grid = if kind == "raw" {
+                            let nx = args.positive_int("grid width")? as usize;
+                            let ny = args.positive_int("grid height")? as usize;
+                            let nz = args.positive_int("grid depth")? as usize;
+                            let bounds = Aabb::from_points(args.vec3()?, args.vec3()?);
+                            DensityGrid::load_raw(&path, nx, ny, nz, bounds)
+                        } else {
+                            DensityGrid::load_text(&path, Aabb::from_points(args.vec3()?, args.vec3()?))
+                        };
+                        Arc::new(grid.map_err(|e| args.error(format!("cannot load grid {}: {}", path.display(), e)))?)
+                    }
+                    "noise" => {
+                        let seed = args.number()? as u32;
+                        let frequency = args.number()?;
+                        let octaves = args.positive_int("octave count")? as u32;
+                        Arc::new(NoiseDensity::new(seed, frequency, octaves))
+                    }
+                    other => return Err(args.error(format!("unknown density type '{other}'"))),
+                };
+                densities.insert(name, field);
+            }
+            "volume" => {
+                let name = args.word("object name")?;
+                let boundary = objects.get(name).cloned()
+                    .ok_or_else(|| args.error(format!("unknown object '{name}'")))?;
+                let density = |args: &mut Args| -> Result<Arc<dyn DensityField>, LoadError> {
+                    let name = args.word("density name")?;
+                    densities.get(name).cloned().ok_or_else(|| args.error(format!("unknown density '{name}'")))
+                };
+                let field = density(&mut args)?;
+                let scale = args.number()?;
+                if scale <= 0.0 {
+                    return Err(args.error("volume density scale must be greater than zero"));
+                }
+                let (_, phase) = args.material(&materials)?;
+                let mut medium = HeterogeneousMedium::new(field, scale, phase);
+                if args.peek() == Some("emission") {
+                    args.pos += 1;
+                    let emission = args.vec3()?;
+                    let field = match args.peek() {
+                        Some(word) if word != "surface" => Some(density(&mut args)?),
+                        _ => None,
+                    };
+                    medium = medium.with_emission(emission, field);
+                }
+                let surface = args.peek() == Some("surface");
+                if surface {
+                    args.pos += 1;
+                }
+                world.add(Box::new(MediumBoundary::new(Box::new(boundary), Arc::new(medium), surface)));
+            }
+            "atmosphere" => {
+                let density = args.positive_number("atmosphere density")?;
+                let phase = args.phase(&materials, &phases)?;
+                camera.atmosphere = Some(Atmosphere::new(density, phase));
+            }
+            "csg" => {
+                let name = args.word("object name")?.to_string();
+                let op = args.word("csg operation")?;
+                let op = CsgOp::from_name(op)
+                    .ok_or_else(|| args.error(format!("unknown csg operation '{op}'")))?;
+                let mut operand = || -> Result<Arc<dyn Hittable>, LoadError> {
+                    let name = args.word("object name")?;
+                    objects.get(name).cloned().ok_or_else(|| args.error(format!("unknown object '{name}'")))
+                };
+                let (left, right) = (operand()?, operand()?);
+                objects.insert(name, Arc::new(Csg::new(op, Box::new(left), Box::new(right))));
+            }
+            "instance" => {
+                let name = args.word("object name")?;
+                let object = objects.get(name).cloned()
+                    .ok_or_else(|| args.error(format!("unknown object '{name}'")))?;
+                let transform = args.transform()?;
+                let instance = Box::new(Transformed::new(object, transform));
+                if args.peek() == Some("move") {
+                    args.pos += 1;
+                    let offset = args.vec3()?;
+                    world.add(Box::new(Moving::new(instance, Vec3::new(0.0, 0.0, 0.0), offset)));
+                } else {
+                    world.add(instance);
+                }
+            }
+            other => return Err(args.error(format!("unknown statement '{other}'"))),
+        }
+        args.finish()?;
+    }
+
+    Ok(Scene { camera, world, display, warnings })
+}
+
+// Instructions de forme, utilisables seules ou dans une définition `object`
+const SHAPES: [&str; 13] = ["sphere", "moving_sphere", "triangle", "quad", "disk", "plane", "box",
+                            "cylinder", "cone", "torus", "march", "pyramid", "mesh"];
+
+// Géométrie produite par une instruction de forme
+struct Shape {
+    objects: Vec<Box<dyn Hittable>>,
+    lights: Vec<Arc<dyn Light>>, // formes émissives à échantillonner directement
+    warnings: Vec<LoadError>,
+}
+
+fn parse_shape(keyword: &str, args: &mut Args, materials: &HashMap<String, Arc<dyn Material>>,
+               emitters: &HashSet<String>, sdfs: &HashMap<String, Arc<dyn Sdf>>,
+               base_dir: &Path) -> Result<Shape, LoadError> {
+    let mut shape = Shape { objects: Vec::new(), lights: Vec::new(), warnings: Vec::new() };
+    match keyword {
+        "sphere" | "moving_sphere" => {
+            let center0 = args.vec3()?;
+            let center1 = if keyword == "moving_sphere" { args.vec3()? } else { center0 };
+            let radius = args.number()?;
+            let (name, material) = args.material(materials)?;
+            let sphere = Arc::new(Sphere::moving(center0, center1, radius, material));
+            if emitters.contains(name) {
+                shape.lights.push(sphere.clone());
+            }
+            shape.objects.push(Box::new(sphere));
+        }
+        "triangle" => {
+            let (v0, v1, v2) = (args.vec3()?, args.vec3()?, args.vec3()?);
+            let (name, material) = args.material(materials)?;
+            let triangle = Arc::new(Triangle::new(v0, v1, v2, material));
+            if emitters.contains(name) {
+                shape.lights.push(triangle.clone());
+            }
+            shape.objects.push(Box::new(triangle));
+        }
+        "quad" => {
+            let (q, u, v) = (args.vec3()?, args.vec3()?, args.vec3()?);
+            if u.cross(&v).length_squared() == 0.0 {
+                return Err(args.error("quad edges must be non-zero and not parallel"));
+            }
+            let (name, material) = args.material(materials)?;
+            let quad = Arc::new(Quad::new(q, u, v, material));
+            if emitters.contains(name) {
+                shape.lights.push(quad.clone());
+            }
+            shape.objects.push(Box::new(quad));
+        }
+        "disk" => {
+            let (center, normal) = (args.vec3()?, args.vec3()?);
+            if normal.length_squared() == 0.0 {
+                return Err(args.error("disk normal must be non-zero"));
+            }
+            let radius = args.number()?;
+            let (name, material) = args.material(materials)?;
+            let disk = Arc::new(Disk::new(center, normal, radius, material));
+            if emitters.contains(name) {
+                shape.lights.push(disk.clone());
+            }
+            shape.objects.push(Box::new(disk));
+        }
+        "plane" => {
+            let (point, normal) = (args.vec3()?, args.vec3()?);
+            if normal.length_squared() == 0.0 {
+                return Err(args.error("plane normal must be non-zero"));
+            }
+            let (_, material) = args.material(materials)?;
+            shape.objects.push(Box::new(Plane::new(point, normal, material)));
+        }
+        "box" => {
+            let (a, b) = (args.vec3()?, args.vec3()?);
+            let (_, material) = args.material(materials)?;
+            shape.objects.push(Box::new(Cuboid::new(a, b, material)));
+        }
+        "cylinder" | "cone" => {
+            let (base, top) = (args.vec3()?, args.vec3()?);
+            if (top - base).length_squared() == 0.0 {
+                return Err(args.error(format!("{keyword} base and top must differ")));
+            }
+            let base_radius = args.number()?;
+            let top_radius = if keyword == "cone" { args.number()? } else { base_radius };
+            let (_, material) = args.material(materials)?;
+            let object: Box<dyn Hittable> = if keyword == "cone" {
+                Box::new(Cone::new(base, top, base_radius, top_radius, material))
+            } else {
+                Box::new(Cylinder::new(base, top, base_radius, material))
+            };
+            shape.objects.push(object);
+        }
+        "torus" => {
+            let (center, axis) = (args.vec3()?, args.vec3()?);
+            let (major_radius, minor_radius) = (args.number()?, args.number()?);
+            let (_, material) = args.material(materials)?;
+            shape.objects.push(Box::new(Torus::new(center, axis, major_radius, minor_radius, material)));
+        }
+        "march" => {
+            let name = args.word("sdf name")?;
+            let sdf = sdfs.get(name).cloned()
+                .ok_or_else(|| args.error(format!("unknown sdf '{name}'")))?;
+            let (_, material) = args.material(materials)?;
+            shape.objects.push(Box::new(SdfObject::new(sdf, material)));
+        }
+        "pyramid" => {
+            let center = args.vec3()?;
+            let base_width = args.number()?;
+            let height = args.number()?;
+            let rotation = args.vec3()?;
+            let (_, material) = args.material(materials)?;
+            shape.objects.push(Box::new(Pyramid::new(center, base_width, height,
+                rotation.x().to_radians(), rotation.y().to_radians(), rotation.z().to_radians(),
+                material)));
+        }
+        "mesh" => {
+            let path = base_dir.join(args.word("mesh file")?);
+            let (_, material) = args.material(materials)?;
+            let model = load_obj(&path, material)?;
+            for group in model.groups {
+                shape.objects.push(Box::new(group.mesh));
+            }
+            shape.warnings = model.warnings;
+        }
+        other => return Err(args.error(format!("unknown shape '{other}'"))),
+    }
+    Ok(shape)
+}
+
+// Suite d'une instruction `texture <nom> <motif>` : échelle, options puis couleurs
+fn noise_texture(args: &mut Args, pattern: NoisePattern,
+                 textures: &HashMap<String, Arc<dyn Texture>>) -> Result<NoiseTexture, LoadError> {
+    let scale = args.number()?;
+    let (mut octaves, mut simplex, mut seed) = (6, false, 0);
+    loop {
+        match args.peek() {
+            Some("octaves") => {
+                args.pos += 1;
+                octaves = args.positive_int("octave count")? as u32;
+            }
+            Some("simplex") => {
+                args.pos += 1;
+                simplex = true;
+            }
+            Some("seed") => {
+                args.pos += 1;
+                seed = args.number()? as u32;
+            }
+            _ => break,
+        }
+    }
+    let noise: Arc<dyn Noise> = if simplex { Arc::new(Simplex::new(seed)) } else { Arc::new(Perlin::new(seed)) };
+    let (low, high): (Arc<dyn Texture>, Arc<dyn Texture>) = if args.peek().is_some() {
+        (args.color(textures)?, args.color(textures)?)
+    } else {
+        let (low, high) = pattern.default_colors();
+        (Arc::new(SolidColor::new(low)), Arc::new(SolidColor::new(high)))
+    };
+    Ok(NoiseTexture::new(noise, pattern, scale, octaves, low, high))
+}
+
+// Arguments d'une ligne, consommés dans l'ordre
+struct Args<'a> {
+    words: Vec<&'a str>,
+    pos: usize,
+    file: &'a str,
+    line: usize,
+}
+
+impl<'a> Args<'a> {
+    fn error(&self, message: impl Into<String>) -> LoadError {
+        LoadError::parse(self.file, self.line, message)
+    }
+
+    fn peek(&self) -> Option<&'a str> {
+        self.words.get(self.pos).copied()
+    }
+
+    fn word(&mut self, what: &str) -> Result<&'a str, LoadError> {
+        let word = self.peek().ok_or_else(|| self.error(format!("missing {what}")))?;
+        self.pos += 1;
+        Ok(word)
+    }
+
+    fn number(&mut self) -> Result<f32, LoadError> {
+        let word = self.word("number")?;
+        word.parse::<f32>()
+            .ok()
+            .filter(|v| v.is_finite())
+            .ok_or_else(|| self.error(format!("expected a number, found '{word}'")))
+    }
+
+    fn positive_number(&mut self, what: &str) -> Result<f32, LoadError> {
+        let value = self.number()?;
+        if value <= 0.0 {
+            return Err(self.error(format!("{what} must be greater than zero")));
+        }
+        Ok(value)
+    }
+
+    fn optional_number(&mut self) -> Result<Option<f32>, LoadError> {
+        if self.peek().is_none() {
+            return Ok(None);
+        }
+        self.number().map(Some)
+    }
+
+    fn positive_int(&mut self, what: &str) -> Result<i32, LoadError> {
+        let word = self.word(what)?;
+        word.parse::<i32>()
+            .ok()
+            .filter(|&v| v > 0)
+            .ok_or_else(|| self.error(format!("{what} must be a positive integer, found '{word}'")))
+    }
+
+    fn vec3(&mut self) -> Result<Vec3, LoadError> {
+        Ok(Vec3::new(self.number()?, self.number()?, self.number()?))
+    }
+
+    // Trois nombres ou le nom d'une texture déjà définie
+    fn color(&mut self, textures: &HashMap<String, Arc<dyn Texture>>) -> Result<Arc<dyn Texture>, LoadError> {
+        let word = self.peek().ok_or_else(|| self.error("missing color or texture"))?;
+        if word.parse::<f32>().is_ok() {
+            return Ok(Arc::new(SolidColor::new(self.vec3()?)));
+        }
+        self.pos += 1;
+        textures.get(word).cloned().ok_or_else(|| self.error(format!("unknown texture '{word}'")))
+    }
+
+    fn material(&mut self, materials: &HashMap<String, Arc<dyn Material>>) -> Result<(&'a str, Arc<dyn Material>), LoadError> {
+        let name = self.word("material name")?;
+        let material = materials.get(name).cloned().ok_or_else(|| self.error(format!("unknown material '{name}'")))?;
+        Ok((name, material))
+    }
+
+    // Fonction de phase d'un milieu : seuls isotropic et henyey_greenstein conviennent
+    fn phase(&mut self, materials: &HashMap<String, Arc<dyn Material>>, phases: &HashSet<String>) -> Result<Arc<dyn Material>, LoadError> {
+        let (name, material) = self.material(materials)?;
+        if !phases.contains(name) {
+            return Err(self.error(format!("material '{name}' must be isotropic or henyey_greenstein")));
+        }
+        Ok(material)
+    }
+
+    // Suite de transformations appliquées dans l'ordre d'écriture, angles en degrés
+    fn transform(&mut self) -> Result<Transform, LoadError> {
+        let mut transform = Transform::IDENTITY;
+        while let Some(word) = self.peek() {
+            // `move` suit les transformations d'une instance
+            if word == "move" {
+                break;
+            }
+            self.pos += 1;
+            let next = match word {
+                "translate" => Transform::translate(self.vec3()?),
+                "rotate_x" => Transform::rotate_x(self.number()?.to_radians()),
+                "rotate_y" => Transform::rotate_y(self.number()?.to_radians()),
+                "rotate_z" => Transform::rotate_z(self.number()?.to_radians()),
+                "rotate" => {
+                    let axis = self.vec3()?;
+                    if axis.near_zero() {
+                        return Err(self.error("rotation axis must not be zero"));
+                    }
+                    Transform::rotate(axis, self.number()?.to_radians())
+                }
+                "scale" => {
+                    let x = self.number()?;
+                    // Un seul facteur pour une mise à l'échelle uniforme
+                    let factors = match self.peek().map(|w| w.parse::<f32>().is_ok()) {
+                        Some(true) => Vec3::new(x, self.number()?, self.number()?),
+                        _ => Vec3::new(x, x, x),
+                    };
+                    Transform::scale(factors).ok_or_else(|| self.error("scale factors must not be zero"))?
+                }
+                "matrix" => {
+                    let mut m = [[0.0; 4]; 4];
+                    for row in &mut m {
+                        for value in row.iter_mut() {
+                            *value = self.number()?;
+                        }
+                    }
+                    // Pas de projection : les instances gardent le paramètre t des rayons
+                    if m[3] != [0.0, 0.0, 0.0, 1.0] {
+                        return Err(self.error("matrix last row must be 0 0 0 1"));
+                    }
+                    Transform::new(Matrix4::new(m)).ok_or_else(|| self.error("matrix is not invertible"))?
+                }
+                other => return Err(self.error(format!("unknown transformation '{other}'"))),
+            };
+            transform = transform.then(&next);
+        }
+        Ok(transform)
+    }
+
+    fn finish(&self) -> Result<(), LoadError> {
+        match self.peek() {
+            Some(extra) => Err(self.error(format!("unexpected argument '{extra}'"))),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(source: &str) -> Result<Scene, LoadError> {
+        parse_scene(source, "test.scene", Path::new(""))
+    }
+
+    fn error(source: &str) -> String {
+        match parse(source) {
+            Err(e) => e.to_string(),
+            Ok(_) => panic!("expected an error for {source:?}"),
+        }
+    }
+
+    #[test]
+    fn full_scene() {
+        let scene = parse("\
+# Sol en damier et une lampe
+resolution 320 180
+samples 4
+max_depth 6
+camera 0 1 3  0 0 0  0 1 0  40
+
+texture check checker 0.5 0 0 0 1 1 1
+material floor lambertian check
+material lamp light 4 4 4
+sphere 0 -100 0 100 floor
+sphere 0 2 0 0.5 lamp   # source échantillonnée
+triangle 0 0 0  1 0 0  0 1 0 floor
+").unwrap();
+        let camera = &scene.camera;
+        assert_eq!(camera.image_width, 320);
+        assert!((camera.aspect_ratio - 16.0 / 9.0).abs() < 1e-6);
+        assert_eq!(camera.samples_per_pixel, 4);
+        assert_eq!(camera.max_depth, 6);
+        assert_eq!(camera.vfov, 40.0);
+        assert_eq!(camera.look_from.y(), 1.0);
+        assert_eq!(scene.world.len(), 3);
+        assert_eq!(camera.lights.len(), 1);
+    }
+
+    #[test]
+    fn errors_point_at_the_line() {
+        let cases = [
+            ("cube 1", "unknown statement 'cube'"),
+            ("samples 0", "sample count must be a positive integer, found '0'"),
+            ("resolution 320", "missing height"),
+            ("camera 0 0 x", "expected a number, found 'x'"),
+            ("sphere 0 0", "missing number"),
+            ("sphere 0 0 0 1 nowhere", "unknown material 'nowhere'"),
+            ("material m plastic", "unknown material type 'plastic'"),
+            ("material m lambertian marble", "unknown texture 'marble'"),
+            ("material m lambertian 0.5 0.5 0.5 1 extra", "unexpected argument 'extra'"),
+        ];
+        for (statement, message) in cases {
+            // Commentaire et ligne vide avant l'instruction fautive
+            let source = format!("# scène\n\n{statement}\n");
+            assert_eq!(error(&source), format!("test.scene:3: {message}"));
+        }
+    }
+
+    #[test]
+    fn rejects_degenerate_values() {
+        let setup = "\
+material fog isotropic 1 1 1
+material matte lambertian 0.5 0.5 0.5
+object ball sphere 0 0 0 1 fog
+density cloud noise 1 1 2
+sdf dot sphere 0 0 0 0.4
+sdf dots repeat dot 1 0 0
+";
+        let cases = [
+            ("volume ball cloud 0 fog", "volume density scale must be greater than zero"),
+            ("sdf spiral twist dots 10", "twist needs a shape bounded in x and z"),
+            ("quad 0 0 0  1 0 0  2 0 0 fog", "quad edges must be non-zero and not parallel"),
+            ("quad 0 0 0  0 0 0  0 1 0 fog", "quad edges must be non-zero and not parallel"),
+            ("disk 0 0 0  0 0 0  1 fog", "disk normal must be non-zero"),
+            ("plane 0 0 0  0 0 0 fog", "plane normal must be non-zero"),
+            ("instance ball matrix 1 0 0 0  0 1 0 0  0 0 1 0  0 0 0 2", "matrix last row must be 0 0 0 1"),
+            ("instance ball matrix 1 0 0 0  0 1 0 0  0 0 1 0  0 0 0 0", "matrix last row must be 0 0 0 1"),
+            ("medium ball 0 fog", "medium density must be greater than zero"),
+            ("medium ball 0.5 matte", "material 'matte' must be isotropic or henyey_greenstein"),
+            ("atmosphere -1 fog", "atmosphere density must be greater than zero"),
+            ("atmosphere 0.1 matte", "material 'matte' must be isotropic or henyey_greenstein"),
+        ];
+        for (statement, message) in cases {
+            let line = setup.lines().count() + 1;
+            assert_eq!(error(&format!("{setup}{statement}\n")), format!("test.scene:{line}: {message}"));
+        }
+    }
+}
@@ -1,76 +1,183 @@
 use ray::ray::vec3::{Vec3, Point3};
-use ray::ray::color::write_color;
-use ray::ray::ray::Ray;
 use ray::object::sphere::Sphere;
-use ray::object::pyramid::Pyramid;
+use ray::object::plane::Plane;
 use ray::object::hittable_list::HittableList;
+use ray::object::bvh::Bvh;
 use ray::utils::camera::Camera;
 use ray::object::material::Lambertian;
 use std::sync::Arc;
 use ray::object::material::Metal;
 use ray::object::material::Dielectric;
+use ray::object::texture::SolidColor;
+use ray::loader::scene::load_scene;
+
+mod cli;
+
+use cli::{Command, Options};
+use ray::output::{ImageFormat, BitDepth, Encoding};
+use ray::ray::color::DisplayTransform;
+use ray::utils::image::Image;
+use std::io::{self, BufWriter, Write};
+use std::process;
 
 fn main() {
+    let options = match cli::parse_args(std::env::args().skip(1)) {
+        Ok(Command::Render(options)) => options,
+        Ok(Command::Help) => {
+            print!("{}", cli::USAGE);
+            return;
+        }
+        Err(message) => {
+            eprintln!("error: {message}\n\n{}", cli::USAGE);
+            process::exit(2);
+        }
+    };
+    let format = options.output_format().unwrap_or_else(|message| {
+        eprintln!("error: {message}");
+        process::exit(2);
+    });
+    if format.is_hdr() && options.bit_depth.is_some() {
+        eprintln!("error: --bit-depth only applies to ppm and png images");
+        process::exit(2);
+    }
+
+    // Scène décrite dans un fichier, ou scène intégrée désignée par son nom
+    let (mut camera, world, mut display) = match options.scene.as_deref() {
+        None | Some("default") => {
+            let (camera, world) = default_scene();
+            (camera, world, DisplayTransform::default())
+        }
+        Some(path) => match load_scene(path) {
+            Ok(scene) => {
+                for warning in &scene.warnings {
+                    eprintln!("warning: {warning}");
+                }
+                (scene.camera, scene.world, scene.display)
+            }
+            Err(err) => {
+                eprintln!("error: {err}");
+                process::exit(1);
+            }
+        },
+    };
+    apply_options(&mut camera, &options);
+    if let Some(exposure) = options.exposure {
+        display.exposure = exposure;
+    }
+    if let Some(tone_mapper) = options.tone_mapper {
+        display.tone_mapper = tone_mapper;
+    }
+
+    // Render
+    let world = Bvh::new(world);
+    let encoding = Encoding { depth: options.bit_depth.unwrap_or(BitDepth::Eight), display };
+    let result = render(&mut camera, &world, &options, display, |image| write_image(image, &options, format, encoding));
+    if let Err(err) = result {
+        eprintln!("error: cannot write image: {err}");
+        process::exit(1);
+    }
+}
+
+// Les options de la ligne de commande remplacent les réglages de la scène
+fn apply_options(camera: &mut Camera, options: &Options) {
+    match (options.width, options.height) {
+        (Some(width), Some(height)) => {
+            camera.image_width = width;
+            camera.aspect_ratio = width as f32 / height as f32;
+        }
+        (Some(width), None) => camera.image_width = width,
+        (None, Some(height)) => camera.image_width = ((height as f32 * camera.aspect_ratio).round() as i32).max(1),
+        (None, None) => {}
+    }
+    camera.image_height = ((camera.image_width as f32 / camera.aspect_ratio) as i32).max(1);
+    if let Some(samples) = options.samples {
+        camera.samples_per_pixel = samples;
+    }
+    if let Some(max_depth) = options.max_depth {
+        camera.max_depth = max_depth;
+    }
+    if let Some(threads) = options.threads {
+        camera.threads = threads;
+    }
+    if options.seed.is_some() {
+        camera.seed = options.seed;
+    }
+}
+
+fn write_image(image: &Image, options: &Options, format: ImageFormat, encoding: Encoding) -> io::Result<()> {
+    match &options.output {
+        Some(path) => ray::output::save_image_as(path, image, format, encoding),
+        None => {
+            let mut out = BufWriter::new(io::stdout().lock());
+            ray::output::write_image(&mut out, image, format, encoding)?;
+            out.flush()
+        }
+    }
+}
+
+// Les matériaux inutilisés restent sous la main pour composer la scène
+#[allow(unused_variables)]
+fn default_scene() -> (Camera, HittableList) {
     // World
     let mut world = HittableList::new();
 
     // Matériaux avec des couleurs plus vives
     let material_ground = Arc::new(Lambertian { 
-        albedo: Vec3::new(1.0, 1.0, 1.0),  // Vert vif pour le sol
+        albedo: Arc::new(SolidColor::new(Vec3::new(1.0, 1.0, 1.0))),  // Vert vif pour le sol
         fuzz: 0.0 
     });
 
     let material_left = Arc::new(Metal { 
-        albedo: Vec3::new(0.9, 0.1, 0.1),  // Rouge métallique
+        albedo: Arc::new(SolidColor::new(Vec3::new(0.9, 0.1, 0.1))),  // Rouge métallique
         fuzz: 0.3 
     });
 
     let material_right = Arc::new(Dielectric { 
-        albedo: Vec3::new(0.8, 0.8, 1.0),  // Bleu clair pour le verre
+        albedo: Arc::new(SolidColor::new(Vec3::new(0.8, 0.8, 1.0))),  // Bleu clair pour le verre
         ir: 1.0 / 1.3, 
         fuzz: 0.0 
     });
 
     let material_pyramid = Arc::new(Metal { 
-        albedo: Vec3::new(0.0, 0.0, 1.0),  // Or (couleur dorée)
+        albedo: Arc::new(SolidColor::new(Vec3::new(0.0, 0.0, 1.0))),  // Or (couleur dorée)
         fuzz: 0.2 
     });
 
     let material_gold = Arc::new(Metal { 
-        albedo: Vec3::new(0.9, 0.7, 0.1),  // Or (couleur dorée)
+        albedo: Arc::new(SolidColor::new(Vec3::new(0.9, 0.7, 0.1))),  // Or (couleur dorée)
         fuzz: 0.2 
     });
     
     let material_silver = Arc::new(Metal { 
-        albedo: Vec3::new(0.9, 0.9, 0.9),  // Argent (couleur argentée)
+        albedo: Arc::new(SolidColor::new(Vec3::new(0.9, 0.9, 0.9))),  // Argent (couleur argentée)
         fuzz: 0.2 
     });
 
     let material_mirror = Arc::new(Metal { 
-        albedo: Vec3::new(0.9, 0.9, 0.9),  // Argent (couleur argentée)
+        albedo: Arc::new(SolidColor::new(Vec3::new(0.9, 0.9, 0.9))),  // Argent (couleur argentée)
         fuzz: 0.0 
     });
 
     let material_glass = Arc::new(Dielectric { 
-        albedo: Vec3::new(0.9, 0.9, 0.9),  // Argent (couleur argentée)
-        ir: 1.0 / 1.3, 
+        albedo: Arc::new(SolidColor::new(Vec3::new(0.9, 0.9, 0.9))),  // Argent (couleur argentée)
+        ir: 1.5,
         fuzz: 0.0 
     });
 
     let material_purple = Arc::new(Lambertian {
-        albedo: Vec3::new(0.5, 0.0, 0.5),  
+        albedo: Arc::new(SolidColor::new(Vec3::new(0.5, 0.0, 0.5))),  
         
         fuzz: 0.0
     });
 
     let material_cyan = Arc::new(Metal {
-        albedo: Vec3::new(0.0, 0.8, 0.8),  
+        albedo: Arc::new(SolidColor::new(Vec3::new(0.0, 0.8, 0.8))),  
         
         fuzz: 0.1
     });
 
     
-    world.add(Box::new(Sphere::new(Point3::new(0.0, -100.5, -1.0), 100.0, material_ground)));
+    world.add(Box::new(Plane::new(Point3::new(0.0, -0.5, 0.0), Vec3::new(0.0, 1.0, 0.0), material_ground)));
     //world.add(Box::new(Sphere::new(Point3::new(-1.0, 0.0,-1.0), 0.5, material_purple)));
     //world.add(Box::new(Sphere::new(Point3::new(1.0, 0.0,-1.0), 0.5, material_cyan)));
     world.add(Box::new(Sphere::new(Point3::new(0.0, 0.0,-1.5), 0.5, material_glass)));
@@ -96,11 +203,33 @@ fn main() {
     let look_from = Point3::new(-2.0, 2.0, 1.0);
     let look_at = Point3::new(0.0, 0.0, -1.0);
     let vup = Vec3::new(0.0, 0.5, 0.0);
-    let mut camera = Camera::new(aspect_ratio, image_width, samples_per_pixel, look_from, look_at, vup);
+    let camera = Camera::new(aspect_ratio, image_width, samples_per_pixel, look_from, look_at, vup);
 
-    // Render
-    camera.render(&world);
+    (camera, world)
 }
 
+// `save` est appelé dès la fin du rendu, avant d'attendre la fermeture de l'aperçu
+#[cfg(feature = "window")]
+fn render(camera: &mut Camera, world: &Bvh, options: &Options, display: DisplayTransform, save: impl FnOnce(&Image) -> io::Result<()>) -> io::Result<()> {
+    if options.no_window {
+        return save(&camera.render(world));
+    }
+    let width = camera.image_width as usize;
+    let height = camera.image_height.max(1) as usize;
+    match ray::utils::preview::Preview::new(width, height, display) {
+        Ok(mut preview) => {
+            save(&camera.render_with(world, &mut preview))?;
+            preview.wait();
+            Ok(())
+        }
+        Err(err) => {
+            eprintln!("Aperçu indisponible ({err}), rendu sans fenêtre");
+            save(&camera.render(world))
+        }
+    }
+}
 
- 
\ No newline at end of file
+#[cfg(not(feature = "window"))]
+fn render(camera: &mut Camera, world: &Bvh, _options: &Options, _display: DisplayTransform, save: impl FnOnce(&Image) -> io::Result<()>) -> io::Result<()> {
+    save(&camera.render(world))
+}
@@ -0,0 +1,296 @@
+use crate::ray::ray::Ray;
+use crate::ray::vec3::Point3;
+use crate::ray::hittable::{Hittable, HitRecord};
+use crate::utils::interval::Interval;
+use crate::utils::aabb::Aabb;
+use crate::object::hittable_list::HittableList;
+
+const MAX_LEAF_SIZE: usize = 4;
+const SAH_BINS: usize = 16;
+const MAX_SAH_DEPTH: usize = 64;
+const STACK_SIZE: usize = 128;
+
+// Noeud stocké à plat : l'enfant gauche suit toujours son parent,
+// `start` désigne l'enfant droit pour un noeud interne, ou le premier
+// primitif pour une feuille (`count > 0`).
+#[derive(Clone, Copy)]
+struct BvhNode {
+    bbox: Aabb,
+    start: u32,
+    count: u32,
+    axis: u8,
+}
+
+// Hiérarchie indépendante du type de primitif, réutilisable par les maillages
+pub struct BvhTree {
+    nodes: Vec<BvhNode>,
+}
+
+impl BvhTree {
+    // Construit l'arbre et renvoie l'ordre dans lequel ranger les primitifs
+    pub fn build(boxes: &[Aabb]) -> (BvhTree, Vec<usize>) {
+        let mut indices: Vec<usize> = (0..boxes.len()).collect();
+        let mut tree = BvhTree { nodes: Vec::new() };
+        if !boxes.is_empty() {
+            let centroids: Vec<Point3> = boxes.iter().map(|b| b.centroid()).collect();
+            tree.nodes.reserve(2 * boxes.len() / MAX_LEAF_SIZE + 1);
+            tree.build_node(boxes, &centroids, &mut indices, 0, 0);
+        }
+        (tree, indices)
+    }
+
+    pub fn bounding_box(&self) -> Aabb {
+        self.nodes.first().map_or(Aabb::EMPTY, |node| node.bbox)
+    }
+
+    // Parcourt l'arbre d'avant en arrière ; `hit_primitive` reçoit l'index
+    // (dans l'ordre renvoyé par `build`) et l'intervalle courant, et renvoie
+    // la distance de l'intersection trouvée.
+    pub fn hit<F>(&self, ray: &Ray, ray_t: Interval, mut hit_primitive: F) -> bool
+    where
+        F: FnMut(usize, Interval) -> Option<f32>,
+    {
+        if self.nodes.is_empty() {
+            return false;
+        }
+
+        let direction = ray.direction();
+        let mut hit_anything = false;
+        let mut closest_so_far = ray_t.max;
+        let mut stack = [0u32; STACK_SIZE];
+        let mut stack_len = 1;
+
+        while stack_len > 0 {
+            stack_len -= 1;
+            let node_index = stack[stack_len];
+            let node = &self.nodes[node_index as usize];
+            if !node.bbox.hit(ray, Interval::new(ray_t.min, closest_so_far)) {
+                continue;
+            }
+
+            if node.count > 0 {
+                let start = node.start as usize;
+                for i in start..start + node.count as usize {
+                    if let Some(t) = hit_primitive(i, Interval::new(ray_t.min, closest_so_far)) {
+                        hit_anything = true;
+                        closest_so_far = t;
+                    }
+                }
+            } else {
+                let left = node_index + 1;
+                let right = node.start;
+                // L'enfant le plus proche est empilé en dernier pour être visité en premier
+                if direction[node.axis as usize] < 0.0 {
+                    stack[stack_len] = left;
+                    stack[stack_len + 1] = right;
+                } else {
+                    stack[stack_len] = right;
+                    stack[stack_len + 1] = left;
+                }
+                stack_len += 2;
+            }
+        }
+
+        hit_anything
+    }
+
+    // Variante du parcours qui s'arrête à la première intersection trouvée
+    pub fn occluded<F>(&self, ray: &Ray, ray_t: Interval, mut occludes: F) -> bool
+    where
+        F: FnMut(usize) -> bool,
+    {
+        if self.nodes.is_empty() {
+            return false;
+        }
+
+        let mut stack = [0u32; STACK_SIZE];
+        let mut stack_len = 1;
+
+        while stack_len > 0 {
+            stack_len -= 1;
+            let node_index = stack[stack_len];
+            let node = &self.nodes[node_index as usize];
+            if !node.bbox.hit(ray, ray_t) {
+                continue;
+            }
+
+            if node.count > 0 {
+                let start = node.start as usize;
+                if (start..start + node.count as usize).any(&mut occludes) {
+                    return true;
+                }
+            } else {
+                stack[stack_len] = node_index + 1;
+                stack[stack_len + 1] = node.start;
+                stack_len += 2;
+            }
+        }
+
+        false
+    }
+
+    fn build_node(&mut self, boxes: &[Aabb], centroids: &[Point3], indices: &mut [usize], offset: usize, depth: usize) -> usize {
+        let bbox = indices.iter()
+            .fold(Aabb::EMPTY, |acc, &i| Aabb::surrounding(&acc, &boxes[i]));
+        let node_index = self.nodes.len();
+
+        if indices.len() <= MAX_LEAF_SIZE {
+            self.nodes.push(BvhNode { bbox, start: offset as u32, count: indices.len() as u32, axis: 0 });
+            return node_index;
+        }
+
+        let centroid_bounds = indices.iter()
+            .fold(Aabb::EMPTY, |acc, &i| Aabb::surrounding(&acc, &Aabb::from_points(centroids[i], centroids[i])));
+        let axis = centroid_bounds.longest_axis();
+        // Au-delà d'une certaine profondeur on coupe à la médiane pour borner la pile de parcours
+        let mid = if depth < MAX_SAH_DEPTH {
+            Self::partition(boxes, centroids, indices, &centroid_bounds.axis_interval(axis), axis)
+        } else {
+            Self::median_split(centroids, indices, axis)
+        };
+
+        self.nodes.push(BvhNode { bbox, start: 0, count: 0, axis: axis as u8 });
+        let (left, right) = indices.split_at_mut(mid);
+        self.build_node(boxes, centroids, left, offset, depth + 1);
+        let right_index = self.build_node(boxes, centroids, right, offset + mid, depth + 1);
+        self.nodes[node_index].start = right_index as u32;
+        node_index
+    }
+
+    // Découpe par SAH sur des intervalles réguliers, avec repli sur la médiane
+    fn partition(boxes: &[Aabb], centroids: &[Point3], indices: &mut [usize], extent: &Interval, axis: usize) -> usize {
+        let n = indices.len();
+        if extent.size() <= 0.0 {
+            return Self::median_split(centroids, indices, axis);
+        }
+
+        let bin_of = |i: usize| {
+            let rel = (centroids[i][axis] - extent.min) / extent.size();
+            ((rel * SAH_BINS as f32) as usize).min(SAH_BINS - 1)
+        };
+
+        let mut bin_counts = [0usize; SAH_BINS];
+        let mut bin_boxes = [Aabb::EMPTY; SAH_BINS];
+        for &i in indices.iter() {
+            let b = bin_of(i);
+            bin_counts[b] += 1;
+            bin_boxes[b] = Aabb::surrounding(&bin_boxes[b], &boxes[i]);
+        }
+
+        // Aire cumulée de droite à gauche pour évaluer chaque découpe en O(bins)
+        let mut right_area = [0.0f32; SAH_BINS];
+        let mut right_count = [0usize; SAH_BINS];
+        let mut acc_box = Aabb::EMPTY;
+        let mut acc_count = 0;
+        for b in (1..SAH_BINS).rev() {
+            acc_box = Aabb::surrounding(&acc_box, &bin_boxes[b]);
+            acc_count += bin_counts[b];
+            right_area[b] = acc_box.surface_area();
+            right_count[b] = acc_count;
+        }
+
+        let mut best_split = 0;
+        let mut best_cost = f32::INFINITY;
+        let mut left_box = Aabb::EMPTY;
+        let mut left_count = 0;
+        for split in 1..SAH_BINS {
+            left_box = Aabb::surrounding(&left_box, &bin_boxes[split - 1]);
+            left_count += bin_counts[split - 1];
+            if left_count == 0 || right_count[split] == 0 {
+                continue;
+            }
+            let cost = left_box.surface_area() * left_count as f32
+                + right_area[split] * right_count[split] as f32;
+            if cost < best_cost {
+                best_cost = cost;
+                best_split = split;
+            }
+        }
+
+        if best_split == 0 {
+            return Self::median_split(centroids, indices, axis);
+        }
+
+        let mut mid = 0;
+        for k in 0..n {
+            if bin_of(indices[k]) < best_split {
+                indices.swap(k, mid);
+                mid += 1;
+            }
+        }
+        mid
+    }
+
+    fn median_split(centroids: &[Point3], indices: &mut [usize], axis: usize) -> usize {
+        let mid = indices.len() / 2;
+        indices.select_nth_unstable_by(mid, |&a, &b| centroids[a][axis].total_cmp(&centroids[b][axis]));
+        mid
+    }
+}
+
+// Hiérarchie de volumes englobants utilisable à la place d'une HittableList
+pub struct Bvh {
+    objects: Vec<Box<dyn Hittable>>,
+    tree: BvhTree,
+    unbounded: Vec<Box<dyn Hittable>>, // objets infinis, testés avant l'arbre
+}
+
+impl Bvh {
+    pub fn new(list: HittableList) -> Self {
+        Bvh::from_objects(list.into_objects())
+    }
+
+    pub fn from_objects(objects: Vec<Box<dyn Hittable>>) -> Self {
+        let (objects, unbounded): (Vec<_>, Vec<_>) = objects.into_iter()
+            .partition(|object| object.bounding_box().is_bounded());
+        let boxes: Vec<Aabb> = objects.iter().map(|object| object.bounding_box()).collect();
+        let (tree, order) = BvhTree::build(&boxes);
+
+        let mut slots: Vec<Option<Box<dyn Hittable>>> = objects.into_iter().map(Some).collect();
+        let objects = order.iter()
+            .map(|&i| slots[i].take().expect("each object appears once in the BVH order"))
+            .collect();
+
+        Bvh { objects, tree, unbounded }
+    }
+}
+
+impl From<HittableList> for Bvh {
+    fn from(list: HittableList) -> Self {
+        Bvh::new(list)
+    }
+}
+
+impl Hittable for Bvh {
+    fn hit(&self, ray: Ray, ray_t: Interval, rec: &mut HitRecord) -> bool {
+        let mut temp_rec = HitRecord::new();
+        let mut hit_anything = false;
+        let mut closest_so_far = ray_t.max;
+        for object in &self.unbounded {
+            if object.hit(ray, Interval::new(ray_t.min, closest_so_far), &mut temp_rec) {
+                hit_anything = true;
+                closest_so_far = temp_rec.t.min;
+                *rec = temp_rec.clone();
+            }
+        }
+
+        let hit_tree = self.tree.hit(&ray, Interval::new(ray_t.min, closest_so_far), |i, interval| {
+            if self.objects[i].hit(ray, interval, &mut temp_rec) {
+                *rec = temp_rec.clone();
+                Some(temp_rec.t.min)
+            } else {
+                None
+            }
+        });
+        hit_anything || hit_tree
+    }
+
+    fn bounding_box(&self) -> Aabb {
+        if self.unbounded.is_empty() { self.tree.bounding_box() } else { Aabb::UNIVERSE }
+    }
+
+    fn occluded(&self, ray: Ray, ray_t: Interval) -> bool {
+        self.unbounded.iter().any(|object| object.occluded(ray, ray_t))
+            || self.tree.occluded(&ray, ray_t, |i| self.objects[i].occluded(ray, ray_t))
+    }
+}
@@ -0,0 +1,248 @@
+use crate::ray::ray::Ray;
+use crate::ray::vec3::{Vec3, Point3};
+use crate::ray::hittable::{Hittable, HitRecord};
+use crate::ray::transform::Frame;
+use crate::utils::interval::Interval;
+use crate::utils::aabb::Aabb;
+use crate::object::material::Material;
+use std::f32::consts::PI;
+use std::sync::Arc;
+
+// Cône fermé, éventuellement tronqué, de `base` (rayon `base_radius`) à `top`
+// (rayon `top_radius`, 0 pour une pointe). Sur la paroi u suit l'angle autour
+// de l'axe et v la hauteur ; sur les bouchons v va du centre (0) au bord (1)
+pub struct Cone {
+    pub base: Point3,
+    pub top: Point3,
+    pub base_radius: f32,
+    pub top_radius: f32,
+    pub material: Arc<dyn Material>,
+    frame: Frame,
+    height: f32,
+}
+
+impl Cone {
+    pub fn new(base: Point3, top: Point3, base_radius: f32, top_radius: f32, material: Arc<dyn Material>) -> Self {
+        let axis = top - base;
+        let frame = Frame::new(base, axis);
+        Cone { base, top, base_radius, top_radius, material, frame, height: axis.length() }
+    }
+
+    // Variation du rayon par unité de hauteur
+    fn slope(&self) -> f32 {
+        (self.top_radius - self.base_radius) / self.height
+    }
+
+    fn angle(x: f32, z: f32) -> f32 {
+        let phi = z.atan2(x);
+        let phi = if phi < 0.0 { phi + 2.0 * PI } else { phi };
+        phi / (2.0 * PI)
+    }
+
+    // Plus proche intersection avec la paroi latérale, en coordonnées locales
+    fn hit_side(&self, o: Vec3, d: Vec3, ray_t: Interval) -> Option<(f32, Vec3)> {
+        let k = self.slope();
+        let r = self.base_radius + k * o.y();
+        let a = d.x() * d.x() + d.z() * d.z() - k * k * d.y() * d.y();
+        let half_b = o.x() * d.x() + o.z() * d.z() - k * r * d.y();
+        let c = o.x() * o.x() + o.z() * o.z() - r * r;
+        let (t0, t1) = if a.abs() < 1e-12 {
+            // Rayon parallèle à une génératrice : l'équation devient linéaire. Pour
+            // un cylindre (k = 0) half_b est nul aussi et seuls les bouchons comptent
+            if half_b == 0.0 {
+                return None;
+            }
+            let t = -c / (2.0 * half_b);
+            (t, t)
+        } else {
+            let discriminant = half_b * half_b - a * c;
+            if discriminant < 0.0 {
+                return None;
+            }
+            // Forme stable : la petite racine reste précise quand `a` tend vers 0
+            let q = -(half_b + half_b.signum() * discriminant.sqrt());
+            let (t0, t1) = (q / a, c / q);
+            if t0 <= t1 { (t0, t1) } else { (t1, t0) }
+        };
+        for t in [t0, t1] {
+            if !ray_t.contains(t) {
+                continue;
+            }
+            // La hauteur écarte aussi la nappe opposée du cône
+            let p = o + d * t;
+            if p.y() < 0.0 || p.y() > self.height {
+                continue;
+            }
+            // Gradient de x² + z² - (r0 + k y)²
+            let normal = Vec3::new(p.x(), -k * (self.base_radius + k * p.y()), p.z());
+            return Some((t, normal));
+        }
+        None
+    }
+
+    // Bouchon circulaire de rayon `radius` à la hauteur `y`
+    fn hit_cap(o: Vec3, d: Vec3, y: f32, radius: f32, ray_t: Interval) -> Option<f32> {
+        if radius <= 0.0 || d.y().abs() < 1e-12 {
+            return None;
+        }
+        let t = (y - o.y()) / d.y();
+        if !ray_t.contains(t) {
+            return None;
+        }
+        let p = o + d * t;
+        if p.x() * p.x() + p.z() * p.z() > radius * radius {
+            return None;
+        }
+        Some(t)
+    }
+}
+
+impl Hittable for Cone {
+    fn hit(&self, ray: Ray, ray_t: Interval, rec: &mut HitRecord) -> bool {
+        let o = self.frame.point_to_local(ray.origin());
+        let d = self.frame.to_local(ray.direction());
+
+        // Chaque candidat rétrécit l'intervalle pour ne garder que le plus proche
+        let mut closest = ray_t;
+        let mut found = None;
+        if let Some((t, normal)) = self.hit_side(o, d, closest) {
+            closest.max = t;
+            let p = o + d * t;
+            found = Some((t, normal, Cone::angle(p.x(), p.z()), p.y() / self.height));
+        }
+        let caps = [
+            (0.0, self.base_radius, Vec3::new(0.0, -1.0, 0.0)),
+            (self.height, self.top_radius, Vec3::new(0.0, 1.0, 0.0)),
+        ];
+        for (y, radius, normal) in caps {
+            if let Some(t) = Cone::hit_cap(o, d, y, radius, closest) {
+                closest.max = t;
+                let p = o + d * t;
+                let rho = (p.x() * p.x() + p.z() * p.z()).sqrt() / radius;
+                found = Some((t, normal, Cone::angle(p.x(), p.z()), rho));
+            }
+        }
+
+        let Some((t, normal, u, v)) = found else {
+            return false;
+        };
+        rec.t = Interval::new(t, t);
+        rec.p = ray.at(t);
+        rec.set_face_normal(ray, self.frame.to_world(normal).unit_vector());
+        (rec.u, rec.v) = (u, v);
+        rec.material = Some(self.material.clone());
+        true
+    }
+
+    // Union des boîtes des deux disques d'extrémité
+    fn bounding_box(&self) -> Aabb {
+        let base_extent = self.frame.disk_extent(self.base_radius);
+        let top_extent = self.frame.disk_extent(self.top_radius);
+        Aabb::surrounding(
+            &Aabb::from_points(self.base - base_extent, self.base + base_extent),
+            &Aabb::from_points(self.top - top_extent, self.top + top_extent),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::object::material::Lambertian;
+
+    fn material() -> Arc<dyn Material> {
+        Arc::new(Lambertian::new(Vec3::new(0.5, 0.5, 0.5), 1.0))
+    }
+
+    // Cône tronqué vertical : rayon 1 en y = 0, 0.5 en y = 2
+    fn frustum() -> Cone {
+        Cone::new(Point3::new(0.0, 0.0, 0.0), Point3::new(0.0, 2.0, 0.0), 1.0, 0.5, material())
+    }
+
+    fn hit(cone: &Cone, origin: Point3, direction: Vec3) -> Option<HitRecord> {
+        let mut rec = HitRecord::new();
+        cone.hit(Ray::new(origin, direction), Interval::new(0.001, f32::INFINITY), &mut rec).then_some(rec)
+    }
+
+    #[test]
+    fn truncated_side_hit() {
+        // Rayon 0.75 à mi-hauteur
+        let rec = hit(&frustum(), Point3::new(-5.0, 1.0, 0.0), Vec3::new(1.0, 0.0, 0.0)).expect("side hit");
+        assert!((rec.t.min - 4.25).abs() < 1e-4, "t = {}", rec.t.min);
+        assert!(rec.front_face);
+        // La paroi penche vers l'axe : la normale remonte
+        assert!(rec.normal.x() < 0.0 && rec.normal.y() > 0.0);
+        assert!((rec.normal.y() / -rec.normal.x() - 0.25).abs() < 1e-4);
+        assert!((rec.v - 0.5).abs() < 1e-4);
+    }
+
+    #[test]
+    fn cap_hits() {
+        let cone = frustum();
+        let rec = hit(&cone, Point3::new(0.3, 5.0, 0.0), Vec3::new(0.0, -1.0, 0.0)).expect("top cap");
+        assert!((rec.t.min - 3.0).abs() < 1e-4);
+        assert!((rec.normal - Vec3::new(0.0, 1.0, 0.0)).length() < 1e-4);
+
+        let rec = hit(&cone, Point3::new(0.8, -5.0, 0.0), Vec3::new(0.0, 1.0, 0.0)).expect("base cap");
+        assert!((rec.t.min - 5.0).abs() < 1e-4);
+        assert!((rec.normal - Vec3::new(0.0, -1.0, 0.0)).length() < 1e-4);
+        assert!((rec.v - 0.8).abs() < 1e-4);
+    }
+
+    #[test]
+    fn ray_outside_top_cap_hits_side() {
+        // Plus large que le bouchon du haut : touche la paroi là où le rayon vaut 0.7
+        let rec = hit(&frustum(), Point3::new(0.7, 5.0, 0.0), Vec3::new(0.0, -1.0, 0.0)).expect("side hit");
+        assert!((rec.t.min - 3.8).abs() < 1e-3, "t = {}", rec.t.min);
+        assert!(rec.normal.x() > 0.0);
+    }
+
+    #[test]
+    fn ray_along_axis_hits_caps() {
+        let cone = frustum();
+        let rec = hit(&cone, Point3::new(0.0, 5.0, 0.0), Vec3::new(0.0, -1.0, 0.0)).expect("top cap");
+        assert!((rec.t.min - 3.0).abs() < 1e-4);
+        // Depuis l'intérieur, le rayon sort par le bouchon du bas
+        let rec = hit(&cone, Point3::new(0.0, 1.0, 0.0), Vec3::new(0.0, -1.0, 0.0)).expect("base cap");
+        assert!((rec.t.min - 1.0).abs() < 1e-4);
+        assert!(!rec.front_face);
+    }
+
+    #[test]
+    fn pointed_cone_misses_opposite_nappe() {
+        let cone = Cone::new(Point3::new(0.0, 0.0, 0.0), Point3::new(0.0, 1.0, 0.0), 1.0, 0.0, material());
+        // Au-dessus de la pointe, la nappe opposée ne doit pas compter
+        assert!(hit(&cone, Point3::new(-5.0, 1.5, 0.0), Vec3::new(1.0, 0.0, 0.0)).is_none());
+        assert!(hit(&cone, Point3::new(-5.0, 0.5, 0.0), Vec3::new(1.0, 0.0, 0.0)).is_some());
+    }
+
+    #[test]
+    fn ray_parallel_to_slant_hits_side() {
+        // Génératrice de pente 1 : le rayon (-1, -1, 0) lui est parallèle
+        let cone = Cone::new(Point3::new(0.0, 0.0, 0.0), Point3::new(0.0, 1.0, 0.0), 1.0, 0.0, material());
+        let rec = hit(&cone, Point3::new(1.5, 1.5, 0.0), Vec3::new(-1.0, -1.0, 0.0)).expect("side hit");
+        assert!((rec.t.min - 1.0).abs() < 1e-4, "t = {}", rec.t.min);
+        assert!((rec.p - Point3::new(0.5, 0.5, 0.0)).length() < 1e-4);
+        assert!(rec.front_face);
+    }
+
+    #[test]
+    fn tilted_axis() {
+        let cone = Cone::new(Point3::new(0.0, 0.0, 0.0), Point3::new(2.0, 0.0, 0.0), 0.5, 0.5, material());
+        let rec = hit(&cone, Point3::new(1.0, 5.0, 0.0), Vec3::new(0.0, -1.0, 0.0)).expect("side hit");
+        assert!((rec.t.min - 4.5).abs() < 1e-4);
+        assert!((rec.normal - Vec3::new(0.0, 1.0, 0.0)).length() < 1e-4);
+        let rec = hit(&cone, Point3::new(5.0, 0.2, 0.0), Vec3::new(-1.0, 0.0, 0.0)).expect("top cap");
+        assert!((rec.t.min - 3.0).abs() < 1e-4);
+        assert!((rec.normal - Vec3::new(1.0, 0.0, 0.0)).length() < 1e-4);
+    }
+
+    #[test]
+    fn bounding_box_covers_both_caps() {
+        let bbox = frustum().bounding_box();
+        for (axis, (min, max)) in [(0, (-1.0, 1.0)), (1, (0.0, 2.0)), (2, (-1.0, 1.0))] {
+            let interval = bbox.axis_interval(axis);
+            assert!(interval.min <= min + 1e-4 && interval.max >= max - 1e-4);
+        }
+    }
+}
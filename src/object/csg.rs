@@ -0,0 +1,208 @@
+use crate::ray::ray::Ray;
+use crate::ray::hittable::{Hittable, HitRecord};
+use crate::utils::interval::Interval;
+use crate::utils::aabb::Aabb;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CsgOp {
+    Union,
+    Intersection,
+    Difference, // `left` privé de `right`
+}
+
+impl CsgOp {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "union" => Some(CsgOp::Union),
+            "intersection" => Some(CsgOp::Intersection),
+            "difference" => Some(CsgOp::Difference),
+            _ => None,
+        }
+    }
+
+    fn inside(&self, in_left: bool, in_right: bool) -> bool {
+        match self {
+            CsgOp::Union => in_left || in_right,
+            CsgOp::Intersection => in_left && in_right,
+            CsgOp::Difference => in_left && !in_right,
+        }
+    }
+}
+
+// Combinaison booléenne de deux solides fermés. Les traversées des deux
+// opérandes sont parcourues le long de toute la droite du rayon, et seules
+// celles qui font entrer ou sortir du résultat sont gardées ; les faces
+// creusées par une différence prennent le matériau de `right`
+pub struct Csg {
+    pub op: CsgOp,
+    pub left: Box<dyn Hittable>,
+    pub right: Box<dyn Hittable>,
+    bbox: Aabb,
+}
+
+impl Csg {
+    pub fn new(op: CsgOp, left: Box<dyn Hittable>, right: Box<dyn Hittable>) -> Self {
+        let (a, b) = (left.bounding_box(), right.bounding_box());
+        let bbox = match op {
+            CsgOp::Union => Aabb::surrounding(&a, &b),
+            CsgOp::Intersection => Aabb::overlap(&a, &b),
+            CsgOp::Difference => a,
+        };
+        Csg { op, left, right, bbox }
+    }
+}
+
+impl Hittable for Csg {
+    fn hit(&self, ray: Ray, ray_t: Interval, rec: &mut HitRecord) -> bool {
+        let mut hits = Vec::new();
+        self.intersections(ray, ray_t, &mut hits);
+        match hits.into_iter().next() {
+            Some(first) => {
+                *rec = first;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn intersections(&self, ray: Ray, ray_t: Interval, hits: &mut Vec<HitRecord>) {
+        if self.bbox.is_empty() || !self.bbox.hit(&ray, ray_t) {
+            return;
+        }
+        // L'état intérieur/extérieur n'est connu qu'en partant de l'infini
+        let (mut left, mut right) = (Vec::new(), Vec::new());
+        self.left.intersections(ray, Interval::UNIVERSE, &mut left);
+        self.right.intersections(ray, Interval::UNIVERSE, &mut right);
+
+        let (mut in_left, mut in_right) = (false, false);
+        let mut inside = false;
+        let (mut left, mut right) = (left.into_iter().peekable(), right.into_iter().peekable());
+        loop {
+            let from_left = match (left.peek(), right.peek()) {
+                (Some(a), Some(b)) => a.t.min <= b.t.min,
+                (Some(_), None) => true,
+                (None, Some(_)) => false,
+                (None, None) => return,
+            };
+            let mut rec = if from_left {
+                let rec = left.next().unwrap();
+                in_left = rec.front_face;
+                rec
+            } else {
+                let rec = right.next().unwrap();
+                in_right = rec.front_face;
+                rec
+            };
+
+            let now_inside = self.op.inside(in_left, in_right);
+            if now_inside == inside {
+                continue;
+            }
+            inside = now_inside;
+            if ray_t.contains(rec.t.min) {
+                // La normale garde son sens face au rayon ; seul le côté change
+                rec.front_face = now_inside;
+                hits.push(rec);
+            } else if rec.t.min > ray_t.max {
+                return;
+            }
+        }
+    }
+
+    fn bounding_box(&self) -> Aabb {
+        self.bbox
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ray::vec3::{Vec3, Point3};
+    use crate::object::material::Lambertian;
+    use crate::object::sphere::Sphere;
+    use crate::object::cuboid::Cuboid;
+    use std::sync::Arc;
+
+    // Sphère de rayon 1 centrée en (x, 0, 0)
+    fn sphere(x: f32) -> Box<dyn Hittable> {
+        let material = Arc::new(Lambertian::new(Vec3::new(0.5, 0.5, 0.5), 1.0));
+        Box::new(Sphere::new(Point3::new(x, 0.0, 0.0), 1.0, material))
+    }
+
+    // Cube d'arête 2 centré en (x, 0, 0)
+    fn cube(x: f32) -> Box<dyn Hittable> {
+        let material = Arc::new(Lambertian::new(Vec3::new(0.5, 0.5, 0.5), 1.0));
+        Box::new(Cuboid::new(Point3::new(x - 1.0, -1.0, -1.0), Point3::new(x + 1.0, 1.0, 1.0), material))
+    }
+
+    // Traversées (t, front_face) d'un rayon suivant +x depuis `x`
+    fn crossings(csg: &Csg, x: f32) -> Vec<(f32, bool)> {
+        let ray = Ray::new(Point3::new(x, 0.0, 0.0), Vec3::new(1.0, 0.0, 0.0));
+        let mut hits = Vec::new();
+        csg.intersections(ray, Interval::new(0.001, f32::INFINITY), &mut hits);
+        hits.iter().map(|rec| (rec.t.min, rec.front_face)).collect()
+    }
+
+    fn assert_crossings(found: Vec<(f32, bool)>, expected: &[(f32, bool)]) {
+        assert_eq!(found.len(), expected.len(), "found {found:?}, expected {expected:?}");
+        for ((t, front), (want_t, want_front)) in found.iter().zip(expected) {
+            assert!((t - want_t).abs() < 1e-4 && front == want_front, "found {found:?}, expected {expected:?}");
+        }
+    }
+
+    #[test]
+    fn union_of_overlapping_spheres() {
+        // Gauche sur [-1, 1], droite sur [0, 2]
+        let csg = Csg::new(CsgOp::Union, sphere(0.0), sphere(1.0));
+        assert_crossings(crossings(&csg, -10.0), &[(9.0, true), (12.0, false)]);
+    }
+
+    #[test]
+    fn intersection_of_overlapping_spheres() {
+        let csg = Csg::new(CsgOp::Intersection, sphere(0.0), sphere(1.0));
+        assert_crossings(crossings(&csg, -10.0), &[(10.0, true), (11.0, false)]);
+    }
+
+    #[test]
+    fn difference_of_overlapping_spheres() {
+        // Sortie par la face creusée, à l'entrée de la sphère de droite
+        let csg = Csg::new(CsgOp::Difference, sphere(0.0), sphere(1.0));
+        assert_crossings(crossings(&csg, -10.0), &[(9.0, true), (10.0, false)]);
+        // Dans l'autre sens, on entre par la face creusée
+        let ray = Ray::new(Point3::new(10.0, 0.0, 0.0), Vec3::new(-1.0, 0.0, 0.0));
+        let mut rec = HitRecord::new();
+        assert!(csg.hit(ray, Interval::new(0.001, f32::INFINITY), &mut rec));
+        assert!((rec.t.min - 10.0).abs() < 1e-4 && rec.front_face);
+        assert!((rec.normal - Vec3::new(1.0, 0.0, 0.0)).length() < 1e-4);
+    }
+
+    #[test]
+    fn disjoint_operands() {
+        // Gauche sur [-1, 1], droite sur [4, 6]
+        let csg = Csg::new(CsgOp::Union, sphere(0.0), sphere(5.0));
+        assert_crossings(crossings(&csg, -10.0), &[(9.0, true), (11.0, false), (14.0, true), (16.0, false)]);
+
+        let csg = Csg::new(CsgOp::Intersection, cube(0.0), cube(5.0));
+        assert!(csg.bounding_box().is_empty());
+        assert_crossings(crossings(&csg, -10.0), &[]);
+
+        let csg = Csg::new(CsgOp::Difference, cube(0.0), cube(5.0));
+        assert_crossings(crossings(&csg, -10.0), &[(9.0, true), (11.0, false)]);
+    }
+
+    #[test]
+    fn ray_starting_inside_an_operand() {
+        // Depuis x = 0, dans la sphère de gauche : seule la sortie est vue
+        let csg = Csg::new(CsgOp::Union, sphere(0.0), sphere(1.0));
+        assert_crossings(crossings(&csg, 0.0), &[(2.0, false)]);
+
+        let csg = Csg::new(CsgOp::Intersection, sphere(0.0), sphere(1.0));
+        assert_crossings(crossings(&csg, 0.5), &[(0.5, false)]);
+
+        let csg = Csg::new(CsgOp::Difference, sphere(0.0), sphere(1.0));
+        assert_crossings(crossings(&csg, -0.5), &[(0.5, false)]);
+
+        let csg = Csg::new(CsgOp::Difference, cube(0.0), cube(1.0));
+        assert_crossings(crossings(&csg, 1.5), &[]);
+    }
+}
@@ -0,0 +1,105 @@
+use crate::ray::ray::Ray;
+use crate::ray::vec3::{Vec3, Point3};
+use crate::ray::hittable::{Hittable, HitRecord};
+use crate::utils::interval::Interval;
+use crate::utils::aabb::Aabb;
+use crate::object::material::Material;
+use std::sync::Arc;
+
+// Pavé aligné sur les axes (à orienter avec `Transformed`) ; chaque face porte
+// ses propres coordonnées (u, v) de 0 à 1
+pub struct Cuboid {
+    pub min: Point3,
+    pub max: Point3,
+    pub material: Arc<dyn Material>,
+}
+
+impl Cuboid {
+    // Deux coins opposés, dans n'importe quel ordre
+    pub fn new(a: Point3, b: Point3, material: Arc<dyn Material>) -> Self {
+        Cuboid {
+            min: Point3::new(a.x().min(b.x()), a.y().min(b.y()), a.z().min(b.z())),
+            max: Point3::new(a.x().max(b.x()), a.y().max(b.y()), a.z().max(b.z())),
+            material,
+        }
+    }
+
+    // Intervalle paramétrique traversant le pavé, avec l'axe de la face
+    // d'entrée et celui de la face de sortie
+    fn slabs(&self, ray: &Ray) -> Option<(f32, usize, f32, usize)> {
+        let origin = ray.origin();
+        let direction = ray.direction();
+        let (mut t_near, mut near_axis) = (f32::NEG_INFINITY, 0);
+        let (mut t_far, mut far_axis) = (f32::INFINITY, 0);
+        for axis in 0..3 {
+            let inv = 1.0 / direction[axis];
+            let t0 = (self.min[axis] - origin[axis]) * inv;
+            let t1 = (self.max[axis] - origin[axis]) * inv;
+            let (lo, hi) = if t0 < t1 { (t0, t1) } else { (t1, t0) };
+            if lo > t_near {
+                t_near = lo;
+                near_axis = axis;
+            }
+            if hi < t_far {
+                t_far = hi;
+                far_axis = axis;
+            }
+        }
+        if t_near > t_far {
+            return None;
+        }
+        Some((t_near, near_axis, t_far, far_axis))
+    }
+
+    fn fill_record(&self, ray: Ray, t: f32, axis: usize, rec: &mut HitRecord) {
+        let p = ray.at(t);
+        // Face du côté du point touché le long de l'axe
+        let center = 0.5 * (self.min[axis] + self.max[axis]);
+        let mut outward_normal = Vec3::new(0.0, 0.0, 0.0);
+        outward_normal[axis] = if p[axis] > center { 1.0 } else { -1.0 };
+
+        let (a, b) = ((axis + 1) % 3, (axis + 2) % 3);
+        let extent = |k: usize| (self.max[k] - self.min[k]).max(1e-8);
+        rec.t = Interval::new(t, t);
+        rec.p = p;
+        rec.set_face_normal(ray, outward_normal);
+        rec.u = ((p[a] - self.min[a]) / extent(a)).clamp(0.0, 1.0);
+        rec.v = ((p[b] - self.min[b]) / extent(b)).clamp(0.0, 1.0);
+        rec.material = Some(self.material.clone());
+    }
+}
+
+impl Hittable for Cuboid {
+    fn hit(&self, ray: Ray, ray_t: Interval, rec: &mut HitRecord) -> bool {
+        let Some((t_near, near_axis, t_far, far_axis)) = self.slabs(&ray) else {
+            return false;
+        };
+        let (t, axis) = if ray_t.contains(t_near) {
+            (t_near, near_axis)
+        } else if ray_t.contains(t_far) {
+            (t_far, far_axis)
+        } else {
+            return false;
+        };
+
+        self.fill_record(ray, t, axis, rec);
+        true
+    }
+
+    fn intersections(&self, ray: Ray, ray_t: Interval, hits: &mut Vec<HitRecord>) {
+        let Some((t_near, near_axis, t_far, far_axis)) = self.slabs(&ray) else {
+            return;
+        };
+        for (t, axis) in [(t_near, near_axis), (t_far, far_axis)] {
+            if ray_t.contains(t) {
+                let mut rec = HitRecord::new();
+                self.fill_record(ray, t, axis, &mut rec);
+                hits.push(rec);
+            }
+        }
+    }
+
+    fn bounding_box(&self) -> Aabb {
+        Aabb::from_points(self.min, self.max)
+    }
+}
@@ -0,0 +1,76 @@
+use crate::ray::ray::Ray;
+use crate::ray::vec3::Point3;
+use crate::ray::hittable::{Hittable, HitRecord};
+use crate::utils::interval::Interval;
+use crate::utils::aabb::Aabb;
+use crate::object::material::Material;
+use crate::object::cone::Cone;
+use std::sync::Arc;
+
+// Cylindre fermé d'axe `base` -> `top` : un cône dont les deux rayons sont égaux
+pub struct Cylinder {
+    pub base: Point3,
+    pub top: Point3,
+    pub radius: f32,
+    cone: Cone,
+}
+
+impl Cylinder {
+    pub fn new(base: Point3, top: Point3, radius: f32, material: Arc<dyn Material>) -> Self {
+        Cylinder { base, top, radius, cone: Cone::new(base, top, radius, radius, material) }
+    }
+}
+
+impl Hittable for Cylinder {
+    fn hit(&self, ray: Ray, ray_t: Interval, rec: &mut HitRecord) -> bool {
+        self.cone.hit(ray, ray_t, rec)
+    }
+
+    fn bounding_box(&self) -> Aabb {
+        self.cone.bounding_box()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ray::vec3::Vec3;
+    use crate::object::material::Lambertian;
+
+    fn cylinder() -> Cylinder {
+        let material = Arc::new(Lambertian::new(Vec3::new(0.5, 0.5, 0.5), 1.0));
+        Cylinder::new(Point3::new(0.0, 0.0, 0.0), Point3::new(0.0, 2.0, 0.0), 1.0, material)
+    }
+
+    fn hit(origin: Point3, direction: Vec3) -> Option<HitRecord> {
+        let mut rec = HitRecord::new();
+        cylinder().hit(Ray::new(origin, direction), Interval::new(0.001, f32::INFINITY), &mut rec).then_some(rec)
+    }
+
+    #[test]
+    fn side_hit() {
+        let rec = hit(Point3::new(-5.0, 1.0, 0.0), Vec3::new(1.0, 0.0, 0.0)).expect("side hit");
+        assert!((rec.t.min - 4.0).abs() < 1e-4);
+        assert!((rec.normal - Vec3::new(-1.0, 0.0, 0.0)).length() < 1e-4);
+        assert!(hit(Point3::new(-5.0, 2.5, 0.0), Vec3::new(1.0, 0.0, 0.0)).is_none());
+    }
+
+    #[test]
+    fn ray_along_axis_hits_caps() {
+        // Parallèle à la paroi : seuls les bouchons peuvent être touchés
+        let rec = hit(Point3::new(0.5, 5.0, 0.0), Vec3::new(0.0, -1.0, 0.0)).expect("top cap");
+        assert!((rec.t.min - 3.0).abs() < 1e-4);
+        assert!((rec.normal - Vec3::new(0.0, 1.0, 0.0)).length() < 1e-4);
+        let rec = hit(Point3::new(0.5, -5.0, 0.0), Vec3::new(0.0, 1.0, 0.0)).expect("base cap");
+        assert!((rec.t.min - 5.0).abs() < 1e-4);
+        assert!((rec.normal - Vec3::new(0.0, -1.0, 0.0)).length() < 1e-4);
+        assert!(hit(Point3::new(1.5, 5.0, 0.0), Vec3::new(0.0, -1.0, 0.0)).is_none());
+    }
+
+    #[test]
+    fn ray_from_inside_hits_far_wall() {
+        let rec = hit(Point3::new(0.0, 1.0, 0.0), Vec3::new(1.0, 0.0, 0.0)).expect("side hit");
+        assert!((rec.t.min - 1.0).abs() < 1e-4);
+        assert!(!rec.front_face);
+    }
+}
@@ -0,0 +1,112 @@
+use crate::ray::ray::Ray;
+use crate::ray::vec3::{Vec3, Point3};
+use crate::ray::hittable::{Hittable, HitRecord};
+use crate::ray::vec3::dot;
+use crate::ray::transform::Frame;
+use crate::utils::interval::Interval;
+use crate::utils::aabb::Aabb;
+use crate::object::material::Material;
+use crate::object::light::{Light, LightSample, orthonormal_basis};
+use crate::utils::utils::random_double;
+use std::f32::consts::PI;
+use std::sync::Arc;
+
+// Disque plein ; u suit l'angle autour de la normale, v va du centre (0) au bord (1)
+pub struct Disk {
+    pub center: Point3,
+    pub normal: Vec3,
+    pub radius: f32,
+    pub material: Arc<dyn Material>,
+    tangent: Vec3,
+    bitangent: Vec3,
+}
+
+impl Disk {
+    pub fn new(center: Point3, normal: Vec3, radius: f32, material: Arc<dyn Material>) -> Self {
+        let normal = normal.unit_vector();
+        let (tangent, bitangent) = orthonormal_basis(normal);
+        Disk { center, normal, radius, material, tangent, bitangent }
+    }
+
+    fn uv(&self, p: Point3) -> (f32, f32) {
+        let local = p - self.center;
+        let phi = dot(local, self.bitangent).atan2(dot(local, self.tangent));
+        let phi = if phi < 0.0 { phi + 2.0 * PI } else { phi };
+        (phi / (2.0 * PI), local.length() / self.radius)
+    }
+
+    fn area(&self) -> f32 {
+        PI * self.radius * self.radius
+    }
+}
+
+impl Hittable for Disk {
+    fn hit(&self, ray: Ray, ray_t: Interval, rec: &mut HitRecord) -> bool {
+        let denom = dot(self.normal, ray.direction());
+        if denom.abs() < 1e-8 {
+            return false;
+        }
+        let t = dot(self.normal, self.center - ray.origin()) / denom;
+        if !ray_t.contains(t) {
+            return false;
+        }
+        let p = ray.at(t);
+        if (p - self.center).length_squared() > self.radius * self.radius {
+            return false;
+        }
+
+        rec.t = Interval::new(t, t);
+        rec.p = p;
+        rec.set_face_normal(ray, self.normal);
+        (rec.u, rec.v) = self.uv(p);
+        rec.material = Some(self.material.clone());
+        true
+    }
+
+    fn bounding_box(&self) -> Aabb {
+        let extent = Frame::new(self.center, self.normal).disk_extent(self.radius);
+        Aabb::from_points(self.center - extent, self.center + extent)
+    }
+}
+
+impl Light for Disk {
+    fn sample_li(&self, origin: Point3, time: f32) -> Option<LightSample> {
+        let r = self.radius * random_double().sqrt();
+        let phi = 2.0 * PI * random_double();
+        let point = self.center + self.tangent * (r * phi.cos()) + self.bitangent * (r * phi.sin());
+
+        let to_point = point - origin;
+        let distance = to_point.length();
+        if distance < 1e-4 {
+            return None;
+        }
+        let wi = to_point / distance;
+        let cosine = dot(wi, self.normal).abs();
+        if cosine < 1e-6 {
+            return None;
+        }
+
+        let ray = Ray::with_time(origin, wi, time);
+        let mut rec = HitRecord::new();
+        rec.t = Interval::new(distance, distance);
+        rec.p = point;
+        rec.set_face_normal(ray, self.normal);
+        (rec.u, rec.v) = self.uv(point);
+        Some(LightSample {
+            wi,
+            distance,
+            radiance: self.material.emitted(&ray, &rec),
+            pdf: distance * distance / (cosine * self.area()),
+        })
+    }
+
+    fn pdf(&self, origin: Point3, direction: Vec3, time: f32) -> f32 {
+        let mut rec = HitRecord::new();
+        if !self.hit(Ray::with_time(origin, direction, time), Interval::new(0.001, f32::INFINITY), &mut rec) {
+            return 0.0;
+        }
+        let distance = rec.t.min * direction.length();
+        let cosine = dot(direction.unit_vector(), self.normal).abs();
+        distance * distance / (cosine * self.area())
+    }
+}
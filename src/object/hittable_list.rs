@@ -1,19 +1,40 @@
 use crate::ray::ray::Ray;
 use crate::ray::hittable::{Hittable, HitRecord};
 use crate::utils::interval::Interval;
+use crate::utils::aabb::Aabb;
 
 pub struct HittableList {
     objects: Vec<Box<dyn Hittable>>,
+    bbox: Aabb,
+}
+
+impl Default for HittableList {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl HittableList {
     pub fn new() -> Self {
-        HittableList { objects: Vec::new() }
+        HittableList { objects: Vec::new(), bbox: Aabb::EMPTY }
     }
 
     pub fn add(&mut self, object: Box<dyn Hittable>) {
+        self.bbox = Aabb::surrounding(&self.bbox, &object.bounding_box());
         self.objects.push(object);
     }
+
+    pub fn len(&self) -> usize {
+        self.objects.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.objects.is_empty()
+    }
+
+    pub fn into_objects(self) -> Vec<Box<dyn Hittable>> {
+        self.objects
+    }
 }
 
 // Implémentation du trait Hittable pour HittableList
@@ -24,15 +45,21 @@ impl Hittable for HittableList {
         let mut closest_so_far = ray_t.max;
 
         for object in self.objects.iter() {
-            if object.hit(ray, Interval::new(ray_t.min, closest_so_far), &mut temp_rec) {
-                if temp_rec.t.min < closest_so_far {
-                    hit_anything = true;
-                    closest_so_far = temp_rec.t.min;
-                    *rec = temp_rec.clone();
-                }
+            if object.hit(ray, Interval::new(ray_t.min, closest_so_far), &mut temp_rec)
+                && temp_rec.t.min < closest_so_far {
+                hit_anything = true;
+                closest_so_far = temp_rec.t.min;
+                *rec = temp_rec.clone();
             }
         }
         hit_anything
     }
-}
 
+    fn bounding_box(&self) -> Aabb {
+        self.bbox
+    }
+
+    fn occluded(&self, ray: Ray, ray_t: Interval) -> bool {
+        self.objects.iter().any(|object| object.occluded(ray, ray_t))
+    }
+}
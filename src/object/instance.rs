@@ -0,0 +1,83 @@
+use crate::ray::ray::Ray;
+use crate::ray::vec3::Point3;
+use crate::ray::hittable::{Hittable, HitRecord};
+use crate::ray::transform::Transform;
+use crate::utils::interval::Interval;
+use crate::utils::aabb::Aabb;
+
+// Objet placé dans la scène par une transformation affine. Les rayons sont
+// ramenés dans le repère de l'objet ; avec un `Arc`, la même géométrie peut
+// être instanciée autant de fois que nécessaire sans être dupliquée.
+pub struct Transformed<H: Hittable> {
+    pub object: H,
+    transform: Transform,
+    bbox: Aabb,
+}
+
+impl<H: Hittable> Transformed<H> {
+    pub fn new(object: H, transform: Transform) -> Self {
+        let bbox = transformed_box(&object.bounding_box(), &transform);
+        Transformed { object, transform, bbox }
+    }
+
+    pub fn transform(&self) -> &Transform {
+        &self.transform
+    }
+
+    // La direction n'est pas normalisée, donc `t` est le même dans les deux repères
+    fn local_ray(&self, ray: Ray) -> Ray {
+        Ray::with_time(
+            self.transform.inverse.transform_point(ray.origin()),
+            self.transform.inverse.transform_vector(ray.direction()),
+            ray.time(),
+        )
+    }
+
+    fn to_world(&self, rec: &mut HitRecord) {
+        rec.p = self.transform.point(rec.p);
+        // Le produit scalaire avec le rayon garde son signe : `front_face` reste valable
+        rec.normal = self.transform.normal(rec.normal).unit_vector();
+    }
+}
+
+// Boîte englobant les huit coins transformés
+fn transformed_box(bbox: &Aabb, transform: &Transform) -> Aabb {
+    if !bbox.is_bounded() {
+        return Aabb::UNIVERSE;
+    }
+    let mut result = Aabb::EMPTY;
+    for corner in 0..8 {
+        let x = if corner & 1 == 0 { bbox.x.min } else { bbox.x.max };
+        let y = if corner & 2 == 0 { bbox.y.min } else { bbox.y.max };
+        let z = if corner & 4 == 0 { bbox.z.min } else { bbox.z.max };
+        let p = transform.point(Point3::new(x, y, z));
+        result = Aabb::surrounding(&result, &Aabb::from_points(p, p));
+    }
+    result
+}
+
+impl<H: Hittable> Hittable for Transformed<H> {
+    fn hit(&self, ray: Ray, ray_t: Interval, rec: &mut HitRecord) -> bool {
+        if !self.object.hit(self.local_ray(ray), ray_t, rec) {
+            return false;
+        }
+        self.to_world(rec);
+        true
+    }
+
+    fn intersections(&self, ray: Ray, ray_t: Interval, hits: &mut Vec<HitRecord>) {
+        let first = hits.len();
+        self.object.intersections(self.local_ray(ray), ray_t, hits);
+        for rec in &mut hits[first..] {
+            self.to_world(rec);
+        }
+    }
+
+    fn bounding_box(&self) -> Aabb {
+        self.bbox
+    }
+
+    fn occluded(&self, ray: Ray, ray_t: Interval) -> bool {
+        self.object.occluded(self.local_ray(ray), ray_t)
+    }
+}
@@ -0,0 +1,84 @@
+use crate::ray::vec3::{Vec3, Point3};
+use crate::utils::utils::random_double;
+use std::sync::Arc;
+
+// Direction échantillonnée vers une source de lumière
+#[derive(Clone, Copy, Debug)]
+pub struct LightSample {
+    pub wi: Vec3,       // direction unitaire vers le point échantillonné
+    pub distance: f32,  // distance jusqu'à ce point le long de `wi`
+    pub radiance: Vec3, // lumière émise vers l'origine
+    pub pdf: f32,       // densité par angle solide
+}
+
+// Émetteur pouvant être échantillonné directement depuis un point de la scène ;
+// `time` est l'instant du rayon, pour les émetteurs en mouvement
+pub trait Light: Send + Sync {
+    fn sample_li(&self, origin: Point3, time: f32) -> Option<LightSample>;
+
+    // Densité avec laquelle `sample_li` choisirait `direction` depuis `origin`
+    fn pdf(&self, origin: Point3, direction: Vec3, time: f32) -> f32;
+}
+
+impl<T: Light + ?Sized> Light for Arc<T> {
+    fn sample_li(&self, origin: Point3, time: f32) -> Option<LightSample> {
+        (**self).sample_li(origin, time)
+    }
+
+    fn pdf(&self, origin: Point3, direction: Vec3, time: f32) -> f32 {
+        (**self).pdf(origin, direction, time)
+    }
+}
+
+// Ensemble des lumières de la scène, chacune choisie avec la même probabilité
+#[derive(Clone, Default)]
+pub struct LightList {
+    lights: Vec<Arc<dyn Light>>,
+}
+
+impl LightList {
+    pub fn new() -> Self {
+        LightList { lights: Vec::new() }
+    }
+
+    pub fn add(&mut self, light: Arc<dyn Light>) {
+        self.lights.push(light);
+    }
+
+    pub fn len(&self) -> usize {
+        self.lights.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lights.is_empty()
+    }
+}
+
+impl Light for LightList {
+    fn sample_li(&self, origin: Point3, time: f32) -> Option<LightSample> {
+        if self.lights.is_empty() {
+            return None;
+        }
+        let count = self.lights.len();
+        let index = ((random_double() * count as f32) as usize).min(count - 1);
+        let mut sample = self.lights[index].sample_li(origin, time)?;
+        sample.pdf /= count as f32;
+        Some(sample)
+    }
+
+    fn pdf(&self, origin: Point3, direction: Vec3, time: f32) -> f32 {
+        if self.lights.is_empty() {
+            return 0.0;
+        }
+        let sum: f32 = self.lights.iter().map(|light| light.pdf(origin, direction, time)).sum();
+        sum / self.lights.len() as f32
+    }
+}
+
+// Base orthonormée (u, v) complétant le vecteur unitaire `w`
+pub fn orthonormal_basis(w: Vec3) -> (Vec3, Vec3) {
+    let a = if w.x().abs() > 0.9 { Vec3::new(0.0, 1.0, 0.0) } else { Vec3::new(1.0, 0.0, 0.0) };
+    let v = w.cross(&a).unit_vector();
+    let u = w.cross(&v);
+    (u, v)
+}
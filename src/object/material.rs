@@ -1,60 +1,283 @@
 use crate::ray::ray::Ray;
-use crate::ray::vec3::Vec3;
+use crate::ray::vec3::{Vec3, dot};
 use crate::ray::hittable::HitRecord;
+use crate::object::texture::{Texture, SolidColor};
+use crate::object::light::orthonormal_basis;
+use crate::utils::utils::random_double;
+use std::f32::consts::PI;
+use std::sync::Arc;
 
-pub trait Material {
+pub trait Material: Send + Sync {
     fn scatter(&self, r_in: &Ray, rec: &HitRecord, attenuation: &mut Vec3, scattered: &mut Ray) -> bool;
+
+    // Lumière émise au point touché ; noire pour les matériaux non émissifs
+    fn emitted(&self, _r_in: &Ray, _rec: &HitRecord) -> Vec3 {
+        Vec3::new(0.0, 0.0, 0.0)
+    }
+
+    // Densité (par angle solide) avec laquelle `scatter` choisit la direction
+    // `scattered` ; 0 pour les réflexions spéculaires, exclues de l'échantillonnage des lumières
+    fn scattering_pdf(&self, _r_in: &Ray, _rec: &HitRecord, _scattered: &Ray) -> f32 {
+        0.0
+    }
+
+    // BSDF multipliée par le cosinus pour une direction imposée (rayon vers une lumière)
+    fn eval(&self, _r_in: &Ray, _rec: &HitRecord, _scattered: &Ray) -> Vec3 {
+        Vec3::new(0.0, 0.0, 0.0)
+    }
+}
+
+impl Lambertian {
+    pub fn new(albedo: Vec3, fuzz: f32) -> Self {
+        Lambertian::from_texture(Arc::new(SolidColor::new(albedo)), fuzz)
+    }
+
+    pub fn from_texture(albedo: Arc<dyn Texture>, fuzz: f32) -> Self {
+        Lambertian { albedo, fuzz }
+    }
+}
+
+impl Dielectric {
+    pub fn new(albedo: Vec3, ir: f32, fuzz: f32) -> Self {
+        Dielectric::from_texture(Arc::new(SolidColor::new(albedo)), ir, fuzz)
+    }
+
+    pub fn from_texture(albedo: Arc<dyn Texture>, ir: f32, fuzz: f32) -> Self {
+        Dielectric { albedo, fuzz, ir }
+    }
+}
+
+impl Isotropic {
+    pub fn new(albedo: Vec3) -> Self {
+        Isotropic::from_texture(Arc::new(SolidColor::new(albedo)))
+    }
+
+    pub fn from_texture(albedo: Arc<dyn Texture>) -> Self {
+        Isotropic { albedo }
+    }
+}
+
+impl HenyeyGreenstein {
+    pub fn new(albedo: Vec3, g: f32) -> Self {
+        HenyeyGreenstein::from_texture(Arc::new(SolidColor::new(albedo)), g)
+    }
+
+    pub fn from_texture(albedo: Arc<dyn Texture>, g: f32) -> Self {
+        HenyeyGreenstein { albedo, g: g.clamp(-0.99, 0.99) }
+    }
+
+    // Densité pour un angle `cos_theta` avec la direction de propagation
+    fn phase(&self, cos_theta: f32) -> f32 {
+        let g = self.g;
+        let denom = 1.0 + g * g - 2.0 * g * cos_theta;
+        (1.0 - g * g) / (4.0 * PI * denom * denom.max(1e-8).sqrt())
+    }
+}
+
+impl Metal {
+    pub fn new(albedo: Vec3, fuzz: f32) -> Self {
+        Metal::from_texture(Arc::new(SolidColor::new(albedo)), fuzz)
+    }
+
+    pub fn from_texture(albedo: Arc<dyn Texture>, fuzz: f32) -> Self {
+        Metal { albedo, fuzz }
+    }
+}
+
+// Densité de la direction `axis + fuzz * u`, u uniforme sur la sphère unité :
+// somme sur les points de la sphère décalée alignés avec la direction.
+fn fuzzed_direction_pdf(axis: Vec3, fuzz: f32, direction: Vec3) -> f32 {
+    if fuzz <= 0.0 {
+        return 0.0;
+    }
+    let cos_theta = dot(direction.unit_vector(), axis);
+    let discriminant = cos_theta * cos_theta - 1.0 + fuzz * fuzz;
+    if discriminant <= 0.0 {
+        return 0.0;
+    }
+    let sqrt_discriminant = discriminant.sqrt();
+    let roots_squared: f32 = [cos_theta - sqrt_discriminant, cos_theta + sqrt_discriminant]
+        .iter()
+        .filter(|&&t| t > 0.0)
+        .map(|t| t * t)
+        .sum();
+    roots_squared / (4.0 * PI * fuzz * sqrt_discriminant)
 }
 
 #[derive(Clone)]
 pub struct Lambertian {
-    pub albedo: Vec3, // Couleur de diffusion
+    pub albedo: Arc<dyn Texture>, // Couleur de diffusion
     pub fuzz: f32,
 }
 
 #[derive(Clone)]
 pub struct Dielectric {
-    pub albedo: Vec3,
-    pub fuzz : f32,
-    pub ir : f32,
+    pub albedo: Arc<dyn Texture>, // Transmission après une unité de distance dans le matériau (Beer-Lambert)
+    pub fuzz : f32,               // Rugosité de la surface
+    pub ir : f32,                 // Indice de réfraction du matériau (1.5 pour le verre)
 }
 
 #[derive(Clone)]
 pub struct Metal {
-    pub albedo: Vec3,
+    pub albedo: Arc<dyn Texture>,
     pub fuzz : f32,
 }
 
+// Fonctions de phase des milieux participants : le point touché est à
+// l'intérieur du volume, sans normale
+
+// Diffusion uniforme dans toutes les directions
+#[derive(Clone)]
+pub struct Isotropic {
+    pub albedo: Arc<dyn Texture>,
+}
+
+// Diffusion de Henyey-Greenstein : `g` va de -1 (vers l'arrière) à 1 (vers l'avant)
+#[derive(Clone)]
+pub struct HenyeyGreenstein {
+    pub albedo: Arc<dyn Texture>,
+    pub g: f32,
+}
+
+// Source de lumière : émet sur ses deux faces et ne diffuse rien
+#[derive(Clone)]
+pub struct DiffuseLight {
+    pub emit: Vec3,
+}
+
 impl Material for Lambertian {
-    fn scatter(&self, _r_in: &Ray, rec: &HitRecord, attenuation: &mut Vec3, scattered: &mut Ray) -> bool {
+    fn scatter(&self, r_in: &Ray, rec: &HitRecord, attenuation: &mut Vec3, scattered: &mut Ray) -> bool {
         let mut scatter_direction = rec.normal + Vec3::random_unit_vector() * self.fuzz;
         if scatter_direction.near_zero() {
             scatter_direction = rec.normal;
         }
-        *scattered = Ray::new(rec.p, scatter_direction);
-        *attenuation = self.albedo;
+        *scattered = Ray::with_time(rec.p, scatter_direction, r_in.time());
+        *attenuation = self.albedo.value(rec.u, rec.v, rec.p);
         true
     }
+
+    fn scattering_pdf(&self, _r_in: &Ray, rec: &HitRecord, scattered: &Ray) -> f32 {
+        fuzzed_direction_pdf(rec.normal, self.fuzz, scattered.direction())
+    }
+
+    // L'atténuation est constante, donc f * cos = albedo * pdf
+    fn eval(&self, r_in: &Ray, rec: &HitRecord, scattered: &Ray) -> Vec3 {
+        self.albedo.value(rec.u, rec.v, rec.p) * self.scattering_pdf(r_in, rec, scattered)
+    }
 }
 
 impl Material for Dielectric {
     fn scatter(&self, r_in: &Ray, rec: &HitRecord, attenuation: &mut Vec3, scattered: &mut Ray) -> bool {
-        *attenuation = Vec3::new(1.0, 1.0, 1.0);
-        let refraction_ratio = if rec.front_face { 1.0 / self.ir } else { self.ir };
+        // Absorption le long du trajet parcouru à l'intérieur, qui se termine par une face arrière
+        *attenuation = if rec.front_face {
+            Vec3::new(1.0, 1.0, 1.0)
+        } else {
+            let distance = rec.t.min * r_in.direction().length();
+            let albedo = self.albedo.value(rec.u, rec.v, rec.p);
+            Vec3::new(
+                albedo.x().powf(distance),
+                albedo.y().powf(distance),
+                albedo.z().powf(distance),
+            )
+        };
+
+        // La rugosité perturbe la normale de la microfacette touchée ; une
+        // microfacette tournée dos au rayon (incidence rasante) est ignorée
         let unit_direction = r_in.direction().unit_vector();
-        let refracted = unit_direction.refract(&rec.normal, refraction_ratio);
-        *scattered = Ray::new(rec.p, refracted);
+        let mut normal = rec.normal;
+        if self.fuzz > 0.0 {
+            let perturbed = rec.normal + Vec3::random_unit_vector() * self.fuzz;
+            if dot(perturbed, rec.normal) > 0.0 && dot(-unit_direction, perturbed) > 0.0 && !perturbed.near_zero() {
+                normal = perturbed.unit_vector();
+            }
+        }
+
+        let refraction_ratio = if rec.front_face { 1.0 / self.ir } else { self.ir };
+        let cos_theta = f32::min(dot(-unit_direction, normal), 1.0);
+
+        let direction = match unit_direction.refract(&normal, refraction_ratio) {
+            Some(refracted) if random_double() >= fresnel_dielectric(cos_theta, refraction_ratio) => refracted,
+            _ => unit_direction.reflect(&normal),
+        };
+        *scattered = Ray::with_time(rec.p, direction, r_in.time());
         true
     }
 }
 
+// Réflectance de Fresnel exacte (lumière non polarisée) à l'interface entre deux diélectriques
+fn fresnel_dielectric(cos_theta_i: f32, etai_over_etat: f32) -> f32 {
+    let sin2_theta_t = etai_over_etat * etai_over_etat * (1.0 - cos_theta_i * cos_theta_i).max(0.0);
+    if sin2_theta_t >= 1.0 {
+        return 1.0;
+    }
+    let cos_theta_t = (1.0 - sin2_theta_t).sqrt();
+    let r_parallel = (cos_theta_i - etai_over_etat * cos_theta_t) / (cos_theta_i + etai_over_etat * cos_theta_t);
+    let r_perpendicular = (etai_over_etat * cos_theta_i - cos_theta_t) / (etai_over_etat * cos_theta_i + cos_theta_t);
+    0.5 * (r_parallel * r_parallel + r_perpendicular * r_perpendicular)
+}
+
 impl Material for Metal {
     fn scatter(&self, r_in: &Ray, rec: &HitRecord, attenuation: &mut Vec3, scattered: &mut Ray) -> bool {
         let reflected = r_in.direction().reflect(&rec.normal);
-        *scattered = Ray::new(rec.p, reflected + Vec3::random_unit_vector() * self.fuzz);
-        *attenuation = self.albedo;
+        *scattered = Ray::with_time(rec.p, reflected + Vec3::random_unit_vector() * self.fuzz, r_in.time());
+        *attenuation = self.albedo.value(rec.u, rec.v, rec.p);
+        true
+    }
+}
+
+impl Material for Isotropic {
+    fn scatter(&self, r_in: &Ray, rec: &HitRecord, attenuation: &mut Vec3, scattered: &mut Ray) -> bool {
+        *scattered = Ray::with_time(rec.p, Vec3::random_unit_vector(), r_in.time());
+        *attenuation = self.albedo.value(rec.u, rec.v, rec.p);
         true
     }
+
+    fn scattering_pdf(&self, _r_in: &Ray, _rec: &HitRecord, _scattered: &Ray) -> f32 {
+        1.0 / (4.0 * PI)
+    }
+
+    fn eval(&self, r_in: &Ray, rec: &HitRecord, scattered: &Ray) -> Vec3 {
+        self.albedo.value(rec.u, rec.v, rec.p) * self.scattering_pdf(r_in, rec, scattered)
+    }
 }
 
+impl Material for HenyeyGreenstein {
+    fn scatter(&self, r_in: &Ray, rec: &HitRecord, attenuation: &mut Vec3, scattered: &mut Ray) -> bool {
+        // Inversion de la fonction de répartition de l'angle avec la direction incidente
+        let g = self.g;
+        let xi = random_double();
+        let cos_theta = if g.abs() < 1e-3 {
+            1.0 - 2.0 * xi
+        } else {
+            let ratio = (1.0 - g * g) / (1.0 - g + 2.0 * g * xi);
+            ((1.0 + g * g - ratio * ratio) / (2.0 * g)).clamp(-1.0, 1.0)
+        };
+        let sin_theta = (1.0 - cos_theta * cos_theta).max(0.0).sqrt();
+        let phi = 2.0 * PI * random_double();
 
+        let forward = r_in.direction().unit_vector();
+        let (u, v) = orthonormal_basis(forward);
+        let direction = forward * cos_theta + u * (sin_theta * phi.cos()) + v * (sin_theta * phi.sin());
+        *scattered = Ray::with_time(rec.p, direction, r_in.time());
+        *attenuation = self.albedo.value(rec.u, rec.v, rec.p);
+        true
+    }
+
+    fn scattering_pdf(&self, r_in: &Ray, _rec: &HitRecord, scattered: &Ray) -> f32 {
+        self.phase(dot(r_in.direction().unit_vector(), scattered.direction().unit_vector()))
+    }
+
+    fn eval(&self, r_in: &Ray, rec: &HitRecord, scattered: &Ray) -> Vec3 {
+        self.albedo.value(rec.u, rec.v, rec.p) * self.scattering_pdf(r_in, rec, scattered)
+    }
+}
+
+impl Material for DiffuseLight {
+    fn scatter(&self, _r_in: &Ray, _rec: &HitRecord, _attenuation: &mut Vec3, _scattered: &mut Ray) -> bool {
+        false
+    }
+
+    fn emitted(&self, _r_in: &Ray, _rec: &HitRecord) -> Vec3 {
+        self.emit
+    }
+}
@@ -0,0 +1,100 @@
+use crate::ray::ray::Ray;
+use crate::ray::vec3::Vec3;
+use crate::ray::hittable::{Hittable, HitRecord};
+use crate::utils::interval::Interval;
+use crate::utils::aabb::Aabb;
+use crate::object::material::Material;
+use crate::utils::utils::random_double;
+use std::sync::Arc;
+
+// Distance libre avant une interaction dans un milieu de densité `density`
+fn sample_distance(density: f32) -> f32 {
+    -(1.0 - random_double()).ln() / density
+}
+
+// Point de diffusion dans un volume : la normale n'a pas de sens, seule la
+// fonction de phase (`phase`) compte
+pub(crate) fn scatter_record(ray: Ray, t: f32, phase: &Arc<dyn Material>, rec: &mut HitRecord) {
+    rec.t = Interval::new(t, t);
+    rec.p = ray.at(t);
+    rec.normal = Vec3::new(1.0, 0.0, 0.0);
+    rec.front_face = true;
+    (rec.u, rec.v) = (0.0, 0.0);
+    rec.material = Some(phase.clone());
+    rec.medium = None;
+}
+
+// Brouillard ou fumée homogène remplissant un solide fermé (`boundary`) ; les
+// rayons qui le traversent sont diffusés au hasard selon la densité
+pub struct ConstantMedium {
+    pub boundary: Box<dyn Hittable>,
+    pub density: f32, // coefficient d'extinction, par unité de distance
+    pub phase: Arc<dyn Material>,
+}
+
+impl ConstantMedium {
+    pub fn new(boundary: Box<dyn Hittable>, density: f32, phase: Arc<dyn Material>) -> Self {
+        ConstantMedium { boundary, density, phase }
+    }
+}
+
+impl Hittable for ConstantMedium {
+    fn hit(&self, ray: Ray, ray_t: Interval, rec: &mut HitRecord) -> bool {
+        if self.density <= 0.0 || !self.boundary.bounding_box().hit(&ray, ray_t) {
+            return false;
+        }
+        // La distance tirée est consommée portion par portion, ce qui gère
+        // aussi les contours non convexes
+        let length = ray.direction().length();
+        let mut remaining = sample_distance(self.density) / length;
+        for span in self.boundary.spans(ray) {
+            let inside = Interval::overlap(&span.t, &ray_t);
+            if inside.is_empty() {
+                continue;
+            }
+            if remaining < inside.size() {
+                scatter_record(ray, inside.min + remaining, &self.phase, rec);
+                return true;
+            }
+            remaining -= inside.size();
+        }
+        false
+    }
+
+    fn bounding_box(&self) -> Aabb {
+        self.boundary.bounding_box()
+    }
+}
+
+// Atmosphère homogène remplissant toute la scène ; le fond est considéré hors
+// de l'atmosphère, seuls les trajets entre deux surfaces sont voilés
+#[derive(Clone)]
+pub struct Atmosphere {
+    pub density: f32,
+    pub phase: Arc<dyn Material>,
+}
+
+impl Atmosphere {
+    pub fn new(density: f32, phase: Arc<dyn Material>) -> Self {
+        Atmosphere { density, phase }
+    }
+
+    // Remplace `rec` par un point de diffusion si le rayon interagit avec
+    // l'atmosphère avant d'atteindre la surface touchée
+    pub fn scatter(&self, ray: Ray, rec: &mut HitRecord) -> bool {
+        if self.density <= 0.0 {
+            return false;
+        }
+        let t = sample_distance(self.density) / ray.direction().length();
+        if t >= rec.t.min {
+            return false;
+        }
+        scatter_record(ray, t, &self.phase, rec);
+        true
+    }
+
+    // Fraction de lumière transmise sur `distance`
+    pub fn transmittance(&self, distance: f32) -> f32 {
+        (-self.density * distance).exp()
+    }
+}
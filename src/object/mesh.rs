@@ -0,0 +1,145 @@
+use crate::ray::ray::Ray;
+use crate::ray::vec3::{Vec3, Point3, dot};
+use crate::ray::hittable::{Hittable, HitRecord};
+use crate::utils::interval::Interval;
+use crate::utils::aabb::Aabb;
+use crate::object::material::Material;
+use crate::object::triangle::intersect_triangle;
+use crate::object::bvh::BvhTree;
+use std::sync::Arc;
+
+// Données brutes d'un maillage indexé. `normals` et `uvs` sont soit vides,
+// soit de la même taille que `positions` ; `face_materials` est soit vide
+// (matériau 0 partout), soit de la même taille que `triangles`.
+#[derive(Clone, Default)]
+pub struct MeshData {
+    pub positions: Vec<Point3>,
+    pub normals: Vec<Vec3>,
+    pub uvs: Vec<(f32, f32)>,
+    pub triangles: Vec<[u32; 3]>,
+    pub face_materials: Vec<u32>,
+}
+
+// Maillage de triangles partageant leurs sommets, avec sa propre hiérarchie
+pub struct TriangleMesh {
+    positions: Vec<Point3>,
+    normals: Vec<Vec3>,
+    uvs: Vec<(f32, f32)>,
+    triangles: Vec<[u32; 3]>,
+    face_materials: Vec<u32>,
+    materials: Vec<Arc<dyn Material>>,
+    tree: BvhTree,
+}
+
+impl TriangleMesh {
+    pub fn new(data: MeshData, materials: Vec<Arc<dyn Material>>) -> Self {
+        let vertex_count = data.positions.len();
+        assert!(!materials.is_empty(), "a mesh needs at least one material");
+        assert!(data.normals.is_empty() || data.normals.len() == vertex_count,
+            "mesh normals must match the number of positions");
+        assert!(data.uvs.is_empty() || data.uvs.len() == vertex_count,
+            "mesh uvs must match the number of positions");
+        assert!(data.face_materials.is_empty() || data.face_materials.len() == data.triangles.len(),
+            "mesh face materials must match the number of triangles");
+        assert!(data.triangles.iter().flatten().all(|&i| (i as usize) < vertex_count),
+            "mesh triangle index out of range");
+        assert!(data.face_materials.iter().all(|&m| (m as usize) < materials.len()),
+            "mesh material index out of range");
+
+        let boxes: Vec<Aabb> = data.triangles.iter()
+            .map(|&[a, b, c]| {
+                let p = &data.positions;
+                let bbox = Aabb::from_points(p[a as usize], p[b as usize]);
+                Aabb::surrounding(&bbox, &Aabb::from_points(p[c as usize], p[c as usize]))
+            })
+            .collect();
+        let (tree, order) = BvhTree::build(&boxes);
+
+        // Les triangles sont rangés dans l'ordre des feuilles de la hiérarchie
+        let triangles = order.iter().map(|&i| data.triangles[i]).collect();
+        let face_materials = if data.face_materials.is_empty() {
+            Vec::new()
+        } else {
+            order.iter().map(|&i| data.face_materials[i]).collect()
+        };
+
+        TriangleMesh {
+            positions: data.positions,
+            normals: data.normals,
+            uvs: data.uvs,
+            triangles,
+            face_materials,
+            materials,
+            tree,
+        }
+    }
+
+    pub fn triangle_count(&self) -> usize {
+        self.triangles.len()
+    }
+
+    pub fn vertex_count(&self) -> usize {
+        self.positions.len()
+    }
+
+    fn vertices(&self, face: usize) -> (Point3, Point3, Point3) {
+        let [a, b, c] = self.triangles[face];
+        (self.positions[a as usize], self.positions[b as usize], self.positions[c as usize])
+    }
+}
+
+impl Hittable for TriangleMesh {
+    fn hit(&self, ray: Ray, ray_t: Interval, rec: &mut HitRecord) -> bool {
+        let mut closest = None;
+        self.tree.hit(&ray, ray_t, |face, interval| {
+            let (v0, v1, v2) = self.vertices(face);
+            let (t, u, v) = intersect_triangle(&ray, v0, v1, v2, interval)?;
+            closest = Some((face, t, u, v));
+            Some(t)
+        });
+        let Some((face, t, u, v)) = closest else {
+            return false;
+        };
+
+        let [a, b, c] = self.triangles[face];
+        let (a, b, c) = (a as usize, b as usize, c as usize);
+        let (v0, v1, v2) = self.vertices(face);
+        let w = 1.0 - u - v;
+
+        rec.t = Interval::new(t, t);
+        rec.p = ray.at(t);
+        let geometric_normal = (v1 - v0).cross(&(v2 - v0)).unit_vector();
+        rec.set_face_normal(ray, geometric_normal);
+
+        // Normale lissée, ramenée du même côté que la normale géométrique
+        if !self.normals.is_empty() {
+            let shading = self.normals[a] * w + self.normals[b] * u + self.normals[c] * v;
+            if !shading.near_zero() {
+                let shading = shading.unit_vector();
+                rec.normal = if dot(shading, rec.normal) < 0.0 { -shading } else { shading };
+            }
+        }
+
+        (rec.u, rec.v) = if self.uvs.is_empty() {
+            (u, v)
+        } else {
+            let (uv0, uv1, uv2) = (self.uvs[a], self.uvs[b], self.uvs[c]);
+            (uv0.0 * w + uv1.0 * u + uv2.0 * v, uv0.1 * w + uv1.1 * u + uv2.1 * v)
+        };
+
+        let material_index = self.face_materials.get(face).copied().unwrap_or(0) as usize;
+        rec.material = Some(self.materials[material_index].clone());
+        true
+    }
+
+    fn bounding_box(&self) -> Aabb {
+        self.tree.bounding_box()
+    }
+
+    fn occluded(&self, ray: Ray, ray_t: Interval) -> bool {
+        self.tree.occluded(&ray, ray_t, |face| {
+            let (v0, v1, v2) = self.vertices(face);
+            intersect_triangle(&ray, v0, v1, v2, ray_t).is_some()
+        })
+    }
+}
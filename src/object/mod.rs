@@ -2,4 +2,21 @@ pub mod sphere;
 pub mod hittable_list;
 pub mod material;
 pub mod triangle;
-pub mod pyramid;
\ No newline at end of file
+pub mod pyramid;
+pub mod bvh;
+pub mod light;
+pub mod texture;
+pub mod mesh;
+pub mod moving;
+pub mod instance;
+pub mod quad;
+pub mod disk;
+pub mod plane;
+pub mod cuboid;
+pub mod cone;
+pub mod cylinder;
+pub mod torus;
+pub mod csg;
+pub mod sdf;
+pub mod medium;
+pub mod volume;
@@ -0,0 +1,62 @@
+use crate::ray::ray::Ray;
+use crate::ray::vec3::Vec3;
+use crate::ray::hittable::{Hittable, HitRecord};
+use crate::utils::interval::Interval;
+use crate::utils::aabb::Aabb;
+
+// Objet translaté de `offset0` au temps 0 jusqu'à `offset1` au temps 1,
+// immobile en dehors de cet intervalle
+pub struct Moving {
+    object: Box<dyn Hittable>,
+    offset0: Vec3,
+    offset1: Vec3,
+    bbox: Aabb,
+}
+
+impl Moving {
+    pub fn new(object: Box<dyn Hittable>, offset0: Vec3, offset1: Vec3) -> Self {
+        let bbox = object.bounding_box();
+        let bbox = Aabb::surrounding(&bbox.offset(offset0), &bbox.offset(offset1));
+        Moving { object, offset0, offset1, bbox }
+    }
+
+    pub fn offset_at(&self, time: f32) -> Vec3 {
+        let t = time.clamp(0.0, 1.0);
+        self.offset0 * (1.0 - t) + self.offset1 * t
+    }
+
+    // Le rayon est déplacé dans le repère de l'objet plutôt que l'objet lui-même
+    fn local_ray(&self, ray: Ray) -> (Ray, Vec3) {
+        let offset = self.offset_at(ray.time());
+        (Ray::with_time(ray.origin() - offset, ray.direction(), ray.time()), offset)
+    }
+}
+
+impl Hittable for Moving {
+    fn hit(&self, ray: Ray, ray_t: Interval, rec: &mut HitRecord) -> bool {
+        let (local, offset) = self.local_ray(ray);
+        if !self.object.hit(local, ray_t, rec) {
+            return false;
+        }
+        rec.p += offset;
+        true
+    }
+
+    fn intersections(&self, ray: Ray, ray_t: Interval, hits: &mut Vec<HitRecord>) {
+        let (local, offset) = self.local_ray(ray);
+        let first = hits.len();
+        self.object.intersections(local, ray_t, hits);
+        for rec in &mut hits[first..] {
+            rec.p += offset;
+        }
+    }
+
+    fn bounding_box(&self) -> Aabb {
+        self.bbox
+    }
+
+    fn occluded(&self, ray: Ray, ray_t: Interval) -> bool {
+        let (local, _) = self.local_ray(ray);
+        self.object.occluded(local, ray_t)
+    }
+}
@@ -0,0 +1,54 @@
+use crate::ray::ray::Ray;
+use crate::ray::vec3::{Vec3, Point3};
+use crate::ray::hittable::{Hittable, HitRecord};
+use crate::ray::vec3::dot;
+use crate::utils::interval::Interval;
+use crate::utils::aabb::Aabb;
+use crate::object::material::Material;
+use crate::object::light::orthonormal_basis;
+use std::sync::Arc;
+
+// Plan infini passant par `point` ; (u, v) sont les coordonnées du point touché
+// dans le plan, en unités de la scène, ce qui permet de répéter une texture
+pub struct Plane {
+    pub point: Point3,
+    pub normal: Vec3,
+    pub material: Arc<dyn Material>,
+    tangent: Vec3,
+    bitangent: Vec3,
+}
+
+impl Plane {
+    pub fn new(point: Point3, normal: Vec3, material: Arc<dyn Material>) -> Self {
+        let normal = normal.unit_vector();
+        let (tangent, bitangent) = orthonormal_basis(normal);
+        Plane { point, normal, material, tangent, bitangent }
+    }
+}
+
+impl Hittable for Plane {
+    fn hit(&self, ray: Ray, ray_t: Interval, rec: &mut HitRecord) -> bool {
+        let denom = dot(self.normal, ray.direction());
+        if denom.abs() < 1e-8 {
+            return false;
+        }
+        let t = dot(self.normal, self.point - ray.origin()) / denom;
+        if !ray_t.contains(t) {
+            return false;
+        }
+
+        let p = ray.at(t);
+        let local = p - self.point;
+        rec.t = Interval::new(t, t);
+        rec.p = p;
+        rec.set_face_normal(ray, self.normal);
+        (rec.u, rec.v) = (dot(local, self.tangent), dot(local, self.bitangent));
+        rec.material = Some(self.material.clone());
+        true
+    }
+
+    // Non bornée : la BVH teste ces objets à part
+    fn bounding_box(&self) -> Aabb {
+        Aabb::UNIVERSE
+    }
+}
@@ -1,13 +1,14 @@
 use crate::ray::ray::Ray;
-use crate::ray::vec3::{Vec3, Point3};
+use crate::ray::vec3::Point3;
 use crate::ray::hittable::{Hittable, HitRecord};
 use crate::utils::interval::Interval;
+use crate::utils::aabb::Aabb;
 use std::sync::Arc;
 use crate::object::material::Material;
-use crate::object::triangle::Triangle;
+use crate::object::mesh::{MeshData, TriangleMesh};
 
 pub struct Pyramid {
-    triangles: Vec<Triangle>,
+    mesh: TriangleMesh,
 }
 
 impl Pyramid {
@@ -29,39 +30,40 @@ impl Pyramid {
             *point = point.rotate_x(rotation_x);
             *point = point.rotate_y(rotation_y);
             *point = point.rotate_z(rotation_z);
-            *point = *point + center;
+            *point += center;
         }
 
-        let mut triangles = Vec::new();
+        let data = MeshData {
+            positions: vec![p1, p2, p3, p4, apex],
+            triangles: vec![
+                // Four sides
+                // Sens direct vu de l'extérieur : normales sortantes
+                [1, 0, 4], // Front face
+                [2, 1, 4], // Left face
+                [3, 2, 4], // Back face
+                [0, 3, 4], // Right face
 
-        // Four sides
-        triangles.push(Triangle::new(p1, p2, apex, material.clone())); // Front face
-        triangles.push(Triangle::new(p2, p3, apex, material.clone())); // Left face
-        triangles.push(Triangle::new(p3, p4, apex, material.clone())); // Back face
-        triangles.push(Triangle::new(p4, p1, apex, material.clone())); // Right face
+                // Base (made of two triangles)
+                [0, 1, 2], // Base triangle 1
+                [0, 2, 3], // Base triangle 2
+            ],
+            ..MeshData::default()
+        };
 
-        // Base (made of two triangles)
-        triangles.push(Triangle::new(p1, p3, p2, material.clone())); // Base triangle 1
-        triangles.push(Triangle::new(p1, p4, p3, material.clone())); // Base triangle 2
-
-        Pyramid { triangles }
+        Pyramid { mesh: TriangleMesh::new(data, vec![material]) }
     }
 }
 
 impl Hittable for Pyramid {
     fn hit(&self, ray: Ray, ray_t: Interval, rec: &mut HitRecord) -> bool {
-        let mut temp_rec = HitRecord::new();
-        let mut hit_anything = false;
-        let mut closest_so_far = ray_t.max;
+        self.mesh.hit(ray, ray_t, rec)
+    }
 
-        for triangle in &self.triangles {
-            if triangle.hit(ray, Interval::new(ray_t.min, closest_so_far), &mut temp_rec) {
-                hit_anything = true;
-                closest_so_far = temp_rec.t.min;
-                *rec = temp_rec.clone();
-            }
-        }
+    fn bounding_box(&self) -> Aabb {
+        self.mesh.bounding_box()
+    }
 
-        hit_anything
+    fn occluded(&self, ray: Ray, ray_t: Interval) -> bool {
+        self.mesh.occluded(ray, ray_t)
     }
-} 
\ No newline at end of file
+}
\ No newline at end of file
@@ -0,0 +1,119 @@
+use crate::ray::ray::Ray;
+use crate::ray::vec3::{Vec3, Point3};
+use crate::ray::hittable::{Hittable, HitRecord};
+use crate::ray::vec3::dot;
+use crate::utils::interval::Interval;
+use crate::utils::aabb::Aabb;
+use crate::object::material::Material;
+use crate::object::light::{Light, LightSample};
+use crate::utils::utils::random_double;
+use std::sync::Arc;
+
+// Parallélogramme de coin `q` et d'arêtes `u` et `v` ; (u, v) de la texture
+// suivent ces deux arêtes de 0 à 1
+pub struct Quad {
+    pub q: Point3,
+    pub u: Vec3,
+    pub v: Vec3,
+    pub material: Arc<dyn Material>,
+    normal: Vec3,
+    d: f32,
+    w: Vec3,
+    area: f32,
+}
+
+impl Quad {
+    pub fn new(q: Point3, u: Vec3, v: Vec3, material: Arc<dyn Material>) -> Self {
+        let n = u.cross(&v);
+        let normal = n.unit_vector();
+        Quad {
+            q,
+            u,
+            v,
+            material,
+            normal,
+            d: dot(normal, q),
+            w: n / n.length_squared(),
+            area: n.length(),
+        }
+    }
+}
+
+impl Hittable for Quad {
+    fn hit(&self, ray: Ray, ray_t: Interval, rec: &mut HitRecord) -> bool {
+        let denom = dot(self.normal, ray.direction());
+        // Rayon parallèle au plan
+        if denom.abs() < 1e-8 {
+            return false;
+        }
+        let t = (self.d - dot(self.normal, ray.origin())) / denom;
+        if !ray_t.contains(t) {
+            return false;
+        }
+
+        // Coordonnées du point dans la base (u, v)
+        let p = ray.at(t);
+        let planar = p - self.q;
+        let alpha = dot(self.w, planar.cross(&self.v));
+        let beta = dot(self.w, self.u.cross(&planar));
+        if !(0.0..=1.0).contains(&alpha) || !(0.0..=1.0).contains(&beta) {
+            return false;
+        }
+
+        rec.t = Interval::new(t, t);
+        rec.p = p;
+        rec.set_face_normal(ray, self.normal);
+        (rec.u, rec.v) = (alpha, beta);
+        rec.material = Some(self.material.clone());
+        true
+    }
+
+    fn bounding_box(&self) -> Aabb {
+        Aabb::surrounding(
+            &Aabb::from_points(self.q, self.q + self.u + self.v),
+            &Aabb::from_points(self.q + self.u, self.q + self.v),
+        )
+    }
+}
+
+// Échantillonnage uniforme de la surface, densité convertie en angle solide
+impl Light for Quad {
+    fn sample_li(&self, origin: Point3, time: f32) -> Option<LightSample> {
+        let (alpha, beta) = (random_double(), random_double());
+        let point = self.q + self.u * alpha + self.v * beta;
+
+        let to_point = point - origin;
+        let distance = to_point.length();
+        if distance < 1e-4 {
+            return None;
+        }
+        let wi = to_point / distance;
+        let cosine = dot(wi, self.normal).abs();
+        if cosine < 1e-6 {
+            return None;
+        }
+
+        let ray = Ray::with_time(origin, wi, time);
+        let mut rec = HitRecord::new();
+        rec.t = Interval::new(distance, distance);
+        rec.p = point;
+        rec.set_face_normal(ray, self.normal);
+        (rec.u, rec.v) = (alpha, beta);
+        Some(LightSample {
+            wi,
+            distance,
+            radiance: self.material.emitted(&ray, &rec),
+            pdf: distance * distance / (cosine * self.area),
+        })
+    }
+
+    fn pdf(&self, origin: Point3, direction: Vec3, time: f32) -> f32 {
+        let mut rec = HitRecord::new();
+        if !self.hit(Ray::with_time(origin, direction, time), Interval::new(0.001, f32::INFINITY), &mut rec) {
+            return 0.0;
+        }
+        let distance = rec.t.min * direction.length();
+        let cosine = dot(direction.unit_vector(), self.normal).abs();
+        distance * distance / (cosine * self.area)
+    }
+}
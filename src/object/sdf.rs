@@ -0,0 +1,357 @@
+use crate::ray::ray::Ray;
+use crate::ray::vec3::{Vec3, Point3};
+use crate::ray::hittable::{Hittable, HitRecord};
+use crate::utils::interval::Interval;
+use crate::utils::aabb::Aabb;
+use crate::object::material::Material;
+use crate::object::sphere::Sphere;
+use std::sync::Arc;
+
+// Fonction de distance signée : négative à l'intérieur, et jamais plus grande
+// que la vraie distance à la surface divisée par `lipschitz`
+pub trait Sdf: Send + Sync {
+    fn distance(&self, p: Point3) -> f32;
+    fn bounding_box(&self) -> Aabb;
+
+    // Majorant de la pente de `distance` ; les pas de la marche en sont divisés
+    fn lipschitz(&self) -> f32 {
+        1.0
+    }
+}
+
+impl<T: Sdf + ?Sized> Sdf for Arc<T> {
+    fn distance(&self, p: Point3) -> f32 {
+        (**self).distance(p)
+    }
+
+    fn bounding_box(&self) -> Aabb {
+        (**self).bounding_box()
+    }
+
+    fn lipschitz(&self) -> f32 {
+        (**self).lipschitz()
+    }
+}
+
+// Distance quelconque fournie par une fermeture, avec sa boîte englobante
+pub struct SdfFn<F> {
+    pub function: F,
+    pub bbox: Aabb,
+}
+
+impl<F: Fn(Point3) -> f32 + Send + Sync> SdfFn<F> {
+    pub fn new(function: F, bbox: Aabb) -> Self {
+        SdfFn { function, bbox }
+    }
+}
+
+impl<F: Fn(Point3) -> f32 + Send + Sync> Sdf for SdfFn<F> {
+    fn distance(&self, p: Point3) -> f32 {
+        (self.function)(p)
+    }
+
+    fn bounding_box(&self) -> Aabb {
+        self.bbox
+    }
+}
+
+pub struct SdfSphere {
+    pub center: Point3,
+    pub radius: f32,
+}
+
+impl SdfSphere {
+    pub fn new(center: Point3, radius: f32) -> Self {
+        SdfSphere { center, radius }
+    }
+}
+
+impl Sdf for SdfSphere {
+    fn distance(&self, p: Point3) -> f32 {
+        (p - self.center).length() - self.radius
+    }
+
+    fn bounding_box(&self) -> Aabb {
+        let r = Vec3::new(self.radius, self.radius, self.radius);
+        Aabb::from_points(self.center - r, self.center + r)
+    }
+}
+
+// Pavé aligné sur les axes, `half_size` depuis le centre sur chaque axe
+pub struct SdfBox {
+    pub center: Point3,
+    pub half_size: Vec3,
+}
+
+impl SdfBox {
+    pub fn new(center: Point3, half_size: Vec3) -> Self {
+        SdfBox { center, half_size }
+    }
+}
+
+impl Sdf for SdfBox {
+    fn distance(&self, p: Point3) -> f32 {
+        let local = p - self.center;
+        let q = [0, 1, 2].map(|i| local[i].abs() - self.half_size[i]);
+        let outside = Vec3::new(q[0].max(0.0), q[1].max(0.0), q[2].max(0.0)).length();
+        let inside = q[0].max(q[1]).max(q[2]).min(0.0);
+        outside + inside
+    }
+
+    fn bounding_box(&self) -> Aabb {
+        Aabb::from_points(self.center - self.half_size, self.center + self.half_size)
+    }
+}
+
+// Tore d'axe Y
+pub struct SdfTorus {
+    pub center: Point3,
+    pub major_radius: f32,
+    pub minor_radius: f32,
+}
+
+impl SdfTorus {
+    pub fn new(center: Point3, major_radius: f32, minor_radius: f32) -> Self {
+        SdfTorus { center, major_radius, minor_radius }
+    }
+}
+
+impl Sdf for SdfTorus {
+    fn distance(&self, p: Point3) -> f32 {
+        let local = p - self.center;
+        let radial = (local.x() * local.x() + local.z() * local.z()).sqrt() - self.major_radius;
+        (radial * radial + local.y() * local.y()).sqrt() - self.minor_radius
+    }
+
+    fn bounding_box(&self) -> Aabb {
+        let outer = self.major_radius + self.minor_radius;
+        let extent = Vec3::new(outer, self.minor_radius, outer);
+        Aabb::from_points(self.center - extent, self.center + extent)
+    }
+}
+
+// Union adoucie (minimum polynomial) : `smoothness` est la largeur du raccord
+pub struct SmoothUnion {
+    pub a: Arc<dyn Sdf>,
+    pub b: Arc<dyn Sdf>,
+    pub smoothness: f32,
+}
+
+impl SmoothUnion {
+    pub fn new(a: Arc<dyn Sdf>, b: Arc<dyn Sdf>, smoothness: f32) -> Self {
+        SmoothUnion { a, b, smoothness }
+    }
+}
+
+impl Sdf for SmoothUnion {
+    fn distance(&self, p: Point3) -> f32 {
+        let (da, db) = (self.a.distance(p), self.b.distance(p));
+        let k = self.smoothness;
+        if k <= 0.0 {
+            return da.min(db);
+        }
+        let h = (0.5 + 0.5 * (db - da) / k).clamp(0.0, 1.0);
+        db + (da - db) * h - k * h * (1.0 - h)
+    }
+
+    // Le raccord gonfle la surface d'au plus k/4
+    fn bounding_box(&self) -> Aabb {
+        let bbox = Aabb::surrounding(&self.a.bounding_box(), &self.b.bounding_box());
+        let grow = self.smoothness.max(0.0) / 2.0;
+        Aabb::new(bbox.x.expand(grow), bbox.y.expand(grow), bbox.z.expand(grow))
+    }
+
+    fn lipschitz(&self) -> f32 {
+        self.a.lipschitz().max(self.b.lipschitz())
+    }
+}
+
+// Répétition infinie de `shape` avec la période donnée sur chaque axe (0 pour
+// ne pas répéter) ; la forme doit tenir dans une cellule centrée sur l'origine
+pub struct Repeat {
+    pub shape: Arc<dyn Sdf>,
+    pub period: Vec3,
+}
+
+impl Repeat {
+    pub fn new(shape: Arc<dyn Sdf>, period: Vec3) -> Self {
+        Repeat { shape, period }
+    }
+}
+
+impl Sdf for Repeat {
+    fn distance(&self, p: Point3) -> f32 {
+        let mut local = p;
+        for axis in 0..3 {
+            let period = self.period[axis];
+            if period > 0.0 {
+                local[axis] -= period * (local[axis] / period).round();
+            }
+        }
+        self.shape.distance(local)
+    }
+
+    fn bounding_box(&self) -> Aabb {
+        let bbox = self.shape.bounding_box();
+        let axis = |i: usize| if self.period[i] > 0.0 { Interval::UNIVERSE } else { bbox.axis_interval(i) };
+        Aabb::new(axis(0), axis(1), axis(2))
+    }
+
+    fn lipschitz(&self) -> f32 {
+        self.shape.lipschitz()
+    }
+}
+
+// Torsion autour de l'axe Y, `rate` en radians par unité de hauteur ; la forme
+// doit être bornée en x et z, sinon la torsion n'a pas de constante de Lipschitz
+pub struct Twist {
+    pub shape: Arc<dyn Sdf>,
+    pub rate: f32,
+    radius: f32, // plus grande distance à l'axe Y dans la boîte de la forme
+}
+
+impl Twist {
+    pub fn new(shape: Arc<dyn Sdf>, rate: f32) -> Self {
+        let bbox = shape.bounding_box();
+        let x = bbox.x.min.abs().max(bbox.x.max.abs());
+        let z = bbox.z.min.abs().max(bbox.z.max.abs());
+        Twist { shape, rate, radius: (x * x + z * z).sqrt() }
+    }
+}
+
+impl Sdf for Twist {
+    fn distance(&self, p: Point3) -> f32 {
+        let (sin, cos) = (self.rate * p.y()).sin_cos();
+        let x = cos * p.x() + sin * p.z();
+        let z = -sin * p.x() + cos * p.z();
+        self.shape.distance(Point3::new(x, p.y(), z))
+    }
+
+    fn bounding_box(&self) -> Aabb {
+        let bbox = self.shape.bounding_box();
+        let r = Interval::new(-self.radius, self.radius);
+        Aabb::new(r, bbox.y, r)
+    }
+
+    // La torsion étire les distances d'au plus sqrt(1 + (rate r)²)
+    fn lipschitz(&self) -> f32 {
+        let stretch = self.rate * self.radius;
+        self.shape.lipschitz() * (1.0 + stretch * stretch).sqrt()
+    }
+}
+
+// Surface implicite trouvée par lancer de sphères le long du rayon ; u et v
+// suivent la normale comme sur une sphère
+pub struct SdfObject {
+    pub sdf: Arc<dyn Sdf>,
+    pub material: Arc<dyn Material>,
+    pub epsilon: f32,      // distance à la surface considérée comme un contact
+    pub max_steps: u32,
+    pub max_distance: f32, // limite de la marche quand la forme est infinie
+    bbox: Aabb,
+}
+
+impl SdfObject {
+    pub fn new(sdf: Arc<dyn Sdf>, material: Arc<dyn Material>) -> Self {
+        let bbox = sdf.bounding_box();
+        SdfObject { sdf, material, epsilon: 1e-4, max_steps: 512, max_distance: 1000.0, bbox }
+    }
+
+    // Gradient par différences finies sur les sommets d'un tétraèdre
+    fn normal(&self, p: Point3) -> Vec3 {
+        let h = self.epsilon;
+        let offsets = [
+            Vec3::new(1.0, -1.0, -1.0),
+            Vec3::new(-1.0, -1.0, 1.0),
+            Vec3::new(-1.0, 1.0, -1.0),
+            Vec3::new(1.0, 1.0, 1.0),
+        ];
+        let mut gradient = Vec3::new(0.0, 0.0, 0.0);
+        for offset in offsets {
+            gradient += offset * self.sdf.distance(p + offset * h);
+        }
+        if gradient.near_zero() { Vec3::new(0.0, 1.0, 0.0) } else { gradient.unit_vector() }
+    }
+}
+
+impl Hittable for SdfObject {
+    fn hit(&self, ray: Ray, ray_t: Interval, rec: &mut HitRecord) -> bool {
+        let length = ray.direction().length();
+        if length == 0.0 {
+            return false;
+        }
+        let Some(span) = self.bbox.clip(&ray, ray_t) else {
+            return false;
+        };
+        let limit = self.max_distance / length;
+        let (start, end) = (span.min.max(-limit), span.max.min(limit));
+        let epsilon = self.epsilon;
+        let step_scale = 1.0 / (self.sdf.lipschitz() * length);
+        // Constante infinie ou invalide : aucun pas sûr, la marche n'avancerait pas
+        if !step_scale.is_finite() || step_scale <= 0.0 {
+            return false;
+        }
+
+        // On marche du côté de la surface où se trouve le départ, pour suivre
+        // aussi les rayons réfractés à l'intérieur
+        let mut t = start;
+        let first = self.sdf.distance(ray.at(t));
+        let side = if first < 0.0 { -1.0 } else { 1.0 };
+        // Un rayon qui part de la surface ne doit pas la toucher aussitôt
+        let mut armed = first.abs() >= epsilon
+            || side * self.sdf.distance(ray.at(t + 2.0 * epsilon / length)) <= first.abs();
+
+        for _ in 0..self.max_steps {
+            if t > end {
+                return false;
+            }
+            let distance = side * self.sdf.distance(ray.at(t));
+            if distance < epsilon {
+                if armed {
+                    rec.t = Interval::new(t, t);
+                    rec.p = ray.at(t);
+                    let outward_normal = self.normal(rec.p);
+                    rec.set_face_normal(ray, outward_normal);
+                    (rec.u, rec.v) = Sphere::get_sphere_uv(outward_normal);
+                    rec.material = Some(self.material.clone());
+                    return true;
+                }
+                t += epsilon.max(distance) / length;
+            } else {
+                armed = true;
+                t += distance * step_scale;
+            }
+        }
+        false
+    }
+
+    fn bounding_box(&self) -> Aabb {
+        self.bbox
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::object::material::Lambertian;
+
+    fn hits(sdf: Arc<dyn Sdf>, ray: Ray) -> bool {
+        let material = Arc::new(Lambertian::new(Vec3::new(0.5, 0.5, 0.5), 1.0));
+        SdfObject::new(sdf, material).hit(ray, Interval::new(0.001, f32::INFINITY), &mut HitRecord::new())
+    }
+
+    #[test]
+    fn sphere_is_hit() {
+        let ray = Ray::new(Point3::new(0.0, 0.0, 5.0), Vec3::new(0.0, 0.0, -1.0));
+        assert!(hits(Arc::new(SdfSphere::new(Point3::new(0.0, 0.0, 0.0), 1.0)), ray));
+    }
+
+    #[test]
+    fn unbounded_twist_is_not_marched() {
+        // Forme infinie en x : la torsion n'a pas de constante de Lipschitz finie
+        let dots = Arc::new(Repeat::new(Arc::new(SdfSphere::new(Point3::new(0.0, 0.0, 0.0), 0.4)), Vec3::new(1.0, 0.0, 0.0)));
+        let twist = Arc::new(Twist::new(dots, 0.5));
+        assert!(!twist.lipschitz().is_finite());
+        assert!(!hits(twist, Ray::new(Point3::new(0.0, 0.0, 5.0), Vec3::new(0.0, 0.0, -1.0))));
+    }
+}
@@ -1,50 +1,160 @@
 use crate::ray::ray::Ray;
-use crate::ray::vec3::Point3;
+use crate::ray::vec3::{Vec3, Point3};
 use crate::ray::hittable::{Hittable, HitRecord};
 use crate::ray::vec3::dot;
 use crate::utils::interval::Interval;
+use crate::utils::aabb::Aabb;
 use crate::object::material::Material;
+use crate::object::light::{Light, LightSample, orthonormal_basis};
+use crate::utils::utils::random_double;
+use std::f32::consts::PI;
 use std::sync::Arc;
 
 
 pub struct Sphere {
-    pub center: Point3,
+    pub center: Point3, // centre au temps 0
+    pub motion: Vec3,   // déplacement du centre entre les temps 0 et 1
     pub radius: f32,
     pub material: Arc<dyn Material>,
 }
 
 impl Sphere {
     pub fn new(center: Point3, radius: f32, material: Arc<dyn Material>) -> Self {
-        Sphere { center, radius, material }
+        Sphere { center, motion: Vec3::new(0.0, 0.0, 0.0), radius, material }
+    }
+
+    // Sphère allant de `center0` à `center1`, immobile avant 0 et après 1
+    pub fn moving(center0: Point3, center1: Point3, radius: f32, material: Arc<dyn Material>) -> Self {
+        Sphere { center: center0, motion: center1 - center0, radius, material }
+    }
+
+    pub fn center_at(&self, time: f32) -> Point3 {
+        self.center + self.motion * time.clamp(0.0, 1.0)
     }
 }
 
-impl Hittable for Sphere {
-    fn hit(&self, ray: Ray, ray_t: Interval, rec: &mut HitRecord) -> bool {
-        let oc = self.center - ray.origin();
+impl Sphere {
+    // Coordonnées (u, v) d'un point de la sphère unité : u suit la longitude
+    // depuis -X, v va du pôle sud (0) au pôle nord (1)
+    pub fn get_sphere_uv(p: Point3) -> (f32, f32) {
+        let theta = (-p.y()).clamp(-1.0, 1.0).acos();
+        let phi = (-p.z()).atan2(p.x()) + PI;
+        (phi / (2.0 * PI), theta / PI)
+    }
+
+    // 1 - cos de l'angle du cône sous-tendu par la sphère, sans perte de précision
+    fn one_minus_cos_max(&self, distance_squared: f32) -> f32 {
+        let s = self.radius * self.radius / distance_squared;
+        s / (1.0 + (1.0 - s).sqrt())
+    }
+
+    // Entrée et sortie du rayon, sur toute la droite
+    fn roots(&self, ray: Ray, center: Point3) -> Option<(f32, f32)> {
+        let oc = center - ray.origin();
         let a = ray.direction().length_squared();
         let h = dot(ray.direction(), oc);
         let c = oc.length_squared() - self.radius * self.radius;
         let discriminant = h * h - a * c;
-
         if discriminant < 0.0 {
-            return false;
+            return None;
         }
-
         let sqrt_discriminant = discriminant.sqrt();
-        let mut root = (h - sqrt_discriminant) / a;
-        if root < ray_t.min || root > ray_t.max {
-            root = (h + sqrt_discriminant) / a;
-            if root < ray_t.min || root > ray_t.max {
-                return false;
-            }
-        }
+        Some(((h - sqrt_discriminant) / a, (h + sqrt_discriminant) / a))
+    }
 
+    fn fill_record(&self, ray: Ray, center: Point3, root: f32, rec: &mut HitRecord) {
         rec.t = Interval::new(root, root);
         rec.p = ray.at(rec.t.min);
-        let outward_normal = (rec.p - self.center) / self.radius;
+        let outward_normal = (rec.p - center) / self.radius;
         rec.set_face_normal(ray, outward_normal);
+        (rec.u, rec.v) = Sphere::get_sphere_uv(outward_normal);
         rec.material = Some(self.material.clone());
+    }
+
+    fn solid_angle_pdf(&self, center: Point3, origin: Point3) -> f32 {
+        let distance_squared = (center - origin).length_squared();
+        if distance_squared <= self.radius * self.radius {
+            return 1.0 / (4.0 * PI);
+        }
+        1.0 / (2.0 * PI * self.one_minus_cos_max(distance_squared))
+    }
+}
+
+impl Hittable for Sphere {
+    fn hit(&self, ray: Ray, ray_t: Interval, rec: &mut HitRecord) -> bool {
+        let center = self.center_at(ray.time());
+        let Some((near, far)) = self.roots(ray, center) else {
+            return false;
+        };
+        let root = if ray_t.contains(near) {
+            near
+        } else if ray_t.contains(far) {
+            far
+        } else {
+            return false;
+        };
+        self.fill_record(ray, center, root, rec);
         true
     }
-}
\ No newline at end of file
+
+    fn intersections(&self, ray: Ray, ray_t: Interval, hits: &mut Vec<HitRecord>) {
+        let center = self.center_at(ray.time());
+        let Some((near, far)) = self.roots(ray, center) else {
+            return;
+        };
+        for root in [near, far] {
+            if ray_t.contains(root) {
+                let mut rec = HitRecord::new();
+                self.fill_record(ray, center, root, &mut rec);
+                hits.push(rec);
+            }
+        }
+    }
+
+    fn bounding_box(&self) -> Aabb {
+        let rvec = Vec3::new(self.radius, self.radius, self.radius);
+        let end = self.center + self.motion;
+        Aabb::surrounding(
+            &Aabb::from_points(self.center - rvec, self.center + rvec),
+            &Aabb::from_points(end - rvec, end + rvec),
+        )
+    }
+}
+
+// Échantillonnage uniforme du cône de directions qui voit la sphère
+impl Light for Sphere {
+    fn sample_li(&self, origin: Point3, time: f32) -> Option<LightSample> {
+        let center = self.center_at(time);
+        let to_center = center - origin;
+        let distance_squared = to_center.length_squared();
+        let direction = if distance_squared <= self.radius * self.radius {
+            Vec3::random_unit_vector()
+        } else {
+            let (u, v) = orthonormal_basis(to_center.unit_vector());
+            let phi = 2.0 * PI * random_double();
+            let z = 1.0 - random_double() * self.one_minus_cos_max(distance_squared);
+            let sin_theta = (1.0 - z * z).max(0.0).sqrt();
+            u * (phi.cos() * sin_theta) + v * (phi.sin() * sin_theta) + to_center.unit_vector() * z
+        };
+
+        let ray = Ray::with_time(origin, direction, time);
+        let mut rec = HitRecord::new();
+        if !self.hit(ray, Interval::new(0.001, f32::INFINITY), &mut rec) {
+            return None;
+        }
+        Some(LightSample {
+            wi: direction,
+            distance: rec.t.min,
+            radiance: self.material.emitted(&ray, &rec),
+            pdf: self.solid_angle_pdf(center, origin),
+        })
+    }
+
+    fn pdf(&self, origin: Point3, direction: Vec3, time: f32) -> f32 {
+        let mut rec = HitRecord::new();
+        if !self.hit(Ray::with_time(origin, direction, time), Interval::new(0.001, f32::INFINITY), &mut rec) {
+            return 0.0;
+        }
+        self.solid_angle_pdf(self.center_at(time), origin)
+    }
+}
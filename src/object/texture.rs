@@ -0,0 +1,195 @@
+use crate::ray::vec3::{Vec3, Point3};
+use crate::utils::image::Image;
+use crate::utils::noise::{Noise, Perlin};
+use std::io;
+use std::path::Path;
+use std::sync::Arc;
+
+// Couleur d'une surface en fonction des coordonnées (u, v) et du point touché
+pub trait Texture: Send + Sync {
+    fn value(&self, u: f32, v: f32, p: Point3) -> Vec3;
+}
+
+#[derive(Clone)]
+pub struct SolidColor {
+    pub albedo: Vec3,
+}
+
+impl SolidColor {
+    pub fn new(albedo: Vec3) -> Self {
+        SolidColor { albedo }
+    }
+}
+
+impl Texture for SolidColor {
+    fn value(&self, _u: f32, _v: f32, _p: Point3) -> Vec3 {
+        self.albedo
+    }
+}
+
+// Damier dans l'espace (u, v) : `scale` cases par unité de texture
+#[derive(Clone)]
+pub struct CheckerTexture {
+    pub scale: f32,
+    pub even: Arc<dyn Texture>,
+    pub odd: Arc<dyn Texture>,
+}
+
+impl CheckerTexture {
+    pub fn new(scale: f32, even: Arc<dyn Texture>, odd: Arc<dyn Texture>) -> Self {
+        CheckerTexture { scale, even, odd }
+    }
+
+    pub fn from_colors(scale: f32, even: Vec3, odd: Vec3) -> Self {
+        CheckerTexture::new(scale, Arc::new(SolidColor::new(even)), Arc::new(SolidColor::new(odd)))
+    }
+}
+
+impl Texture for CheckerTexture {
+    fn value(&self, u: f32, v: f32, p: Point3) -> Vec3 {
+        let u_index = (u * self.scale).floor() as i64;
+        let v_index = (v * self.scale).floor() as i64;
+        if (u_index + v_index) % 2 == 0 {
+            self.even.value(u, v, p)
+        } else {
+            self.odd.value(u, v, p)
+        }
+    }
+}
+
+// Texture lue dans une image, échantillonnée au plus proche voisin et répétée hors de [0, 1]
+#[derive(Clone)]
+pub struct ImageTexture {
+    image: Arc<Image>,
+}
+
+impl ImageTexture {
+    pub fn new(image: Image) -> Self {
+        ImageTexture { image: Arc::new(image) }
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        Ok(ImageTexture::new(Image::read_ppm(path)?))
+    }
+}
+
+impl Texture for ImageTexture {
+    fn value(&self, u: f32, v: f32, _p: Point3) -> Vec3 {
+        let width = self.image.width();
+        let height = self.image.height();
+        if width == 0 || height == 0 {
+            // Cyan pour signaler une texture manquante
+            return Vec3::new(0.0, 1.0, 1.0);
+        }
+
+        // v = 0 correspond au bas de l'image
+        let u = u - u.floor();
+        let v = 1.0 - (v - v.floor());
+        let i = ((u * width as f32) as usize).min(width - 1);
+        let j = ((v * height as f32) as usize).min(height - 1);
+        self.image.get(i, j)
+    }
+}
+
+// Motif calculé à partir du bruit, ramené dans [0, 1]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NoisePattern {
+    Plain,      // fBm brut
+    Turbulence, // fBm de la valeur absolue, plis sombres
+    Marble,     // veines le long de x, perturbées par la turbulence
+    Wood,       // cernes concentriques autour de l'axe y
+    Granite,    // grains contrastés
+}
+
+impl NoisePattern {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "noise" | "plain" => Some(NoisePattern::Plain),
+            "turbulence" => Some(NoisePattern::Turbulence),
+            "marble" => Some(NoisePattern::Marble),
+            "wood" => Some(NoisePattern::Wood),
+            "granite" => Some(NoisePattern::Granite),
+            _ => None,
+        }
+    }
+
+    // Couleurs par défaut aux deux extrémités du motif
+    pub fn default_colors(&self) -> (Vec3, Vec3) {
+        match self {
+            NoisePattern::Plain | NoisePattern::Turbulence => (Vec3::new(0.0, 0.0, 0.0), Vec3::new(1.0, 1.0, 1.0)),
+            NoisePattern::Marble => (Vec3::new(0.9, 0.9, 0.88), Vec3::new(0.25, 0.25, 0.3)),
+            NoisePattern::Wood => (Vec3::new(0.75, 0.52, 0.3), Vec3::new(0.4, 0.22, 0.1)),
+            NoisePattern::Granite => (Vec3::new(0.7, 0.6, 0.58), Vec3::new(0.1, 0.1, 0.1)),
+        }
+    }
+}
+
+// Texture solide : évaluée au point touché en 3D, sans coordonnées (u, v) ni
+// image en mémoire. `scale` est la fréquence du bruit par unité de la scène ;
+// le motif passe de `low` (0) à `high` (1)
+#[derive(Clone)]
+pub struct NoiseTexture {
+    pub noise: Arc<dyn Noise>,
+    pub pattern: NoisePattern,
+    pub scale: f32,
+    pub octaves: u32,
+    pub low: Arc<dyn Texture>,
+    pub high: Arc<dyn Texture>,
+}
+
+impl NoiseTexture {
+    pub fn new(noise: Arc<dyn Noise>, pattern: NoisePattern, scale: f32, octaves: u32,
+               low: Arc<dyn Texture>, high: Arc<dyn Texture>) -> Self {
+        NoiseTexture { noise, pattern, scale, octaves, low, high }
+    }
+
+    // Bruit de Perlin avec les couleurs par défaut du motif
+    pub fn preset(pattern: NoisePattern, scale: f32) -> Self {
+        let (low, high) = pattern.default_colors();
+        NoiseTexture::new(Arc::new(Perlin::default()), pattern, scale, 6,
+                          Arc::new(SolidColor::new(low)), Arc::new(SolidColor::new(high)))
+    }
+
+    pub fn marble(scale: f32) -> Self {
+        NoiseTexture::preset(NoisePattern::Marble, scale)
+    }
+
+    pub fn wood(scale: f32) -> Self {
+        NoiseTexture::preset(NoisePattern::Wood, scale)
+    }
+
+    pub fn granite(scale: f32) -> Self {
+        NoiseTexture::preset(NoisePattern::Granite, scale)
+    }
+
+    fn pattern_value(&self, p: Point3) -> f32 {
+        let q = p * self.scale;
+        let octaves = self.octaves;
+        let t = match self.pattern {
+            NoisePattern::Plain => 0.5 + self.noise.fbm(q, octaves),
+            NoisePattern::Turbulence => self.noise.turbulence(q, octaves),
+            NoisePattern::Marble => {
+                // Veines sombres et fines aux passages à zéro du sinus
+                let phase = std::f32::consts::PI * q.x() + 6.0 * self.noise.turbulence(q, octaves);
+                (1.0 - phase.sin().abs()).powi(4)
+            }
+            NoisePattern::Wood => {
+                let grain = 0.15 * self.noise.fbm(Point3::new(q.x(), q.y() * 0.1, q.z()), octaves);
+                let rings = q.x().hypot(q.z()) + 0.3 * self.noise.noise(q * 0.3) + grain;
+                (rings - rings.floor()).powf(1.5)
+            }
+            NoisePattern::Granite => {
+                // Grains : seules les crêtes de la turbulence ressortent
+                (self.noise.turbulence(q, octaves) * 4.0 - 0.8).max(0.0).powi(2)
+            }
+        };
+        t.clamp(0.0, 1.0)
+    }
+}
+
+impl Texture for NoiseTexture {
+    fn value(&self, u: f32, v: f32, p: Point3) -> Vec3 {
+        let t = self.pattern_value(p);
+        self.low.value(u, v, p) * (1.0 - t) + self.high.value(u, v, p) * t
+    }
+}
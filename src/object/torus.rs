@@ -0,0 +1,258 @@
+use crate::ray::ray::Ray;
+use crate::ray::vec3::{Vec3, Point3};
+use crate::ray::hittable::{Hittable, HitRecord};
+use crate::ray::transform::Frame;
+use crate::utils::interval::Interval;
+use crate::utils::aabb::Aabb;
+use crate::object::material::Material;
+use std::f32::consts::PI;
+use std::sync::Arc;
+
+// Tore de centre `center` autour de `axis` : cercle de rayon `major_radius`
+// balayé par un tube de rayon `minor_radius`. u suit l'angle autour de l'axe,
+// v l'angle autour du tube
+pub struct Torus {
+    pub center: Point3,
+    pub axis: Vec3,
+    pub major_radius: f32,
+    pub minor_radius: f32,
+    pub material: Arc<dyn Material>,
+    frame: Frame,
+}
+
+impl Torus {
+    pub fn new(center: Point3, axis: Vec3, major_radius: f32, minor_radius: f32, material: Arc<dyn Material>) -> Self {
+        let frame = Frame::new(center, axis);
+        Torus { center, axis: frame.y, major_radius, minor_radius, material, frame }
+    }
+
+    fn angle(y: f32, x: f32) -> f32 {
+        let phi = y.atan2(x);
+        let phi = if phi < 0.0 { phi + 2.0 * PI } else { phi };
+        phi / (2.0 * PI)
+    }
+}
+
+impl Hittable for Torus {
+    fn hit(&self, ray: Ray, ray_t: Interval, rec: &mut HitRecord) -> bool {
+        let length = ray.direction().length();
+        if length == 0.0 {
+            return false;
+        }
+        let o = self.frame.point_to_local(ray.origin());
+        let d = self.frame.to_local(ray.direction()) / length;
+        let (o, d) = (
+            [o.x() as f64, o.y() as f64, o.z() as f64],
+            [d.x() as f64, d.y() as f64, d.z() as f64],
+        );
+        let big = self.major_radius as f64;
+        let small = self.minor_radius as f64;
+
+        // Entrée dans la sphère englobante : l'origine y est ramenée pour que
+        // les coefficients de la quartique restent petits
+        let od = o[0] * d[0] + o[1] * d[1] + o[2] * d[2];
+        let oo = o[0] * o[0] + o[1] * o[1] + o[2] * o[2];
+        let bound = big + small;
+        let discriminant = od * od - (oo - bound * bound);
+        if discriminant < 0.0 {
+            return false;
+        }
+        let shift = -od - discriminant.sqrt();
+        let o = [o[0] + d[0] * shift, o[1] + d[1] * shift, o[2] + d[2] * shift];
+
+        // (|o + t d|² + R² - r²)² = 4R² ((ox + t dx)² + (oz + t dz)²)
+        let f = o[0] * d[0] + o[1] * d[1] + o[2] * d[2];
+        let e = o[0] * o[0] + o[1] * o[1] + o[2] * o[2] + big * big - small * small;
+        let four_r2 = 4.0 * big * big;
+        let roots = solve_quartic(
+            4.0 * f,
+            4.0 * f * f + 2.0 * e - four_r2 * (d[0] * d[0] + d[2] * d[2]),
+            4.0 * f * e - 2.0 * four_r2 * (o[0] * d[0] + o[2] * d[2]),
+            e * e - four_r2 * (o[0] * o[0] + o[2] * o[2]),
+        );
+
+        let Some(t) = roots
+            .into_iter()
+            .map(|root| ((shift + root) / length as f64) as f32)
+            .filter(|&t| ray_t.contains(t))
+            .min_by(f32::total_cmp)
+        else {
+            return false;
+        };
+
+        let p = ray.at(t);
+        let local = self.frame.point_to_local(p);
+        let radial = (local.x() * local.x() + local.z() * local.z()).sqrt();
+        if radial < 1e-8 {
+            return false;
+        }
+        // Du point le plus proche sur le cercle central vers la surface
+        let ring = Vec3::new(local.x(), 0.0, local.z()) * (self.major_radius / radial);
+        let normal = self.frame.to_world(local - ring).unit_vector();
+
+        rec.t = Interval::new(t, t);
+        rec.p = p;
+        rec.set_face_normal(ray, normal);
+        rec.u = Torus::angle(local.z(), local.x());
+        rec.v = Torus::angle(local.y(), radial - self.major_radius);
+        rec.material = Some(self.material.clone());
+        true
+    }
+
+    // Disque de rayon R + r épaissi de r le long de l'axe
+    fn bounding_box(&self) -> Aabb {
+        let a = self.axis;
+        let thickness = Vec3::new(a.x().abs(), a.y().abs(), a.z().abs()) * self.minor_radius;
+        let extent = self.frame.disk_extent(self.major_radius + self.minor_radius) + thickness;
+        Aabb::from_points(self.center - extent, self.center + extent)
+    }
+}
+
+// Racines réelles de x⁴ + a x³ + b x² + c x + d (méthode de Ferrari), affinées
+// par quelques itérations de Newton
+fn solve_quartic(a: f64, b: f64, c: f64, d: f64) -> Vec<f64> {
+    // Forme réduite y⁴ + p y² + q y + r avec x = y - a/4
+    let a2 = a * a;
+    let p = b - 3.0 * a2 / 8.0;
+    let q = c - a * b / 2.0 + a2 * a / 8.0;
+    let r = d - a * c / 4.0 + a2 * b / 16.0 - 3.0 * a2 * a2 / 256.0;
+
+    let mut roots = Vec::with_capacity(4);
+    if q.abs() < 1e-12 {
+        // Bicarrée : z = y²
+        for z in solve_quadratic(p, r) {
+            if z >= 0.0 {
+                let y = z.sqrt();
+                roots.extend([y, -y]);
+            }
+        }
+    } else {
+        // Racine positive de la résolvante m³ + p m² + (p²/4 - r) m - q²/8
+        let m = largest_cubic_root(p, p * p / 4.0 - r, -q * q / 8.0);
+        if m <= 0.0 {
+            return roots;
+        }
+        let s = (2.0 * m).sqrt();
+        roots.extend(solve_quadratic(-s, p / 2.0 + m + q / (2.0 * s)));
+        roots.extend(solve_quadratic(s, p / 2.0 + m - q / (2.0 * s)));
+    }
+
+    for x in &mut roots {
+        *x -= a / 4.0;
+        for _ in 0..2 {
+            let value = (((*x + a) * *x + b) * *x + c) * *x + d;
+            let slope = ((4.0 * *x + 3.0 * a) * *x + 2.0 * b) * *x + c;
+            if slope.abs() > 1e-12 {
+                *x -= value / slope;
+            }
+        }
+    }
+    roots
+}
+
+// Racines réelles de x² + b x + c
+fn solve_quadratic(b: f64, c: f64) -> Vec<f64> {
+    let discriminant = b * b / 4.0 - c;
+    if discriminant < 0.0 {
+        return Vec::new();
+    }
+    let sqrtd = discriminant.sqrt();
+    vec![-b / 2.0 - sqrtd, -b / 2.0 + sqrtd]
+}
+
+// Plus grande racine réelle de x³ + a x² + b x + c (Cardan ou forme trigonométrique)
+fn largest_cubic_root(a: f64, b: f64, c: f64) -> f64 {
+    let p = b - a * a / 3.0;
+    let q = 2.0 * a * a * a / 27.0 - a * b / 3.0 + c;
+    let discriminant = q * q / 4.0 + p * p * p / 27.0;
+    let y = if discriminant > 0.0 {
+        let sqrtd = discriminant.sqrt();
+        (-q / 2.0 + sqrtd).cbrt() + (-q / 2.0 - sqrtd).cbrt()
+    } else {
+        let radius = (-p / 3.0).sqrt();
+        let cos = if radius > 0.0 { (-q / (2.0 * radius * radius * radius)).clamp(-1.0, 1.0) } else { 0.0 };
+        2.0 * radius * (cos.acos() / 3.0).cos()
+    };
+    y - a / 3.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::object::material::Lambertian;
+
+    // Racines triées, comparées une à une à celles attendues
+    fn assert_roots(mut roots: Vec<f64>, expected: &[f64], tolerance: f64) {
+        roots.sort_by(f64::total_cmp);
+        assert_eq!(roots.len(), expected.len(), "roots {roots:?}, expected {expected:?}");
+        for (root, want) in roots.iter().zip(expected) {
+            assert!((root - want).abs() < tolerance, "roots {roots:?}, expected {expected:?}");
+        }
+    }
+
+    fn torus() -> Torus {
+        let material = Arc::new(Lambertian::new(Vec3::new(0.5, 0.5, 0.5), 1.0));
+        Torus::new(Point3::new(0.0, 0.0, 0.0), Vec3::new(0.0, 1.0, 0.0), 1.0, 0.25, material)
+    }
+
+    #[test]
+    fn quartic_with_four_distinct_roots() {
+        // (x - 1)(x - 2)(x - 3)(x - 4)
+        assert_roots(solve_quartic(-10.0, 35.0, -50.0, 24.0), &[1.0, 2.0, 3.0, 4.0], 1e-9);
+    }
+
+    #[test]
+    fn quartic_with_double_root() {
+        // (x - 1)²(x + 2)(x - 3)
+        let roots = solve_quartic(-3.0, -3.0, 11.0, -6.0);
+        for want in [-2.0, 1.0, 3.0] {
+            assert!(roots.iter().any(|root| (root - want).abs() < 1e-4), "roots {roots:?} miss {want}");
+        }
+        for root in roots {
+            assert!([-2.0, 1.0, 3.0].iter().any(|want| (root - want).abs() < 1e-4), "spurious root {root}");
+        }
+    }
+
+    #[test]
+    fn biquadratic_quartic() {
+        // (x² - 1)(x² - 4)
+        assert_roots(solve_quartic(0.0, -5.0, 0.0, 4.0), &[-2.0, -1.0, 1.0, 2.0], 1e-9);
+    }
+
+    #[test]
+    fn quartic_without_real_roots() {
+        // (x² + 1)(x² + 4), puis (x² + 2x + 2)(x² - 2x + 5)
+        assert_roots(solve_quartic(0.0, 5.0, 0.0, 4.0), &[], 0.0);
+        assert_roots(solve_quartic(0.0, 3.0, 6.0, 10.0), &[], 0.0);
+    }
+
+    #[test]
+    fn cubic_largest_root() {
+        // (x + 1)(x - 2)(x - 5)
+        assert!((largest_cubic_root(-6.0, 3.0, 10.0) - 5.0).abs() < 1e-9);
+        // (x - 1)(x² + 1) : une seule racine réelle
+        assert!((largest_cubic_root(-1.0, 1.0, -1.0) - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn ray_through_hole_misses() {
+        let torus = torus();
+        let mut rec = HitRecord::new();
+        let down = Vec3::new(0.0, -1.0, 0.0);
+        assert!(!torus.hit(Ray::new(Point3::new(0.0, 5.0, 0.0), down), Interval::new(0.001, f32::INFINITY), &mut rec));
+        assert!(!torus.hit(Ray::new(Point3::new(0.5, 5.0, 0.3), down), Interval::new(0.001, f32::INFINITY), &mut rec));
+    }
+
+    #[test]
+    fn ray_along_axis_hits_tube() {
+        let torus = torus();
+        let mut rec = HitRecord::new();
+        let ray = Ray::new(Point3::new(1.0, 5.0, 0.0), Vec3::new(0.0, -2.0, 0.0));
+        assert!(torus.hit(ray, Interval::new(0.001, f32::INFINITY), &mut rec));
+        // Sommet du tube en y = r, atteint à t = (5 - 0.25) / 2
+        assert!((rec.t.min - 2.375).abs() < 1e-4, "t = {}", rec.t.min);
+        assert!((rec.p - Point3::new(1.0, 0.25, 0.0)).length() < 1e-4);
+        assert!(rec.front_face);
+        assert!((rec.normal - Vec3::new(0.0, 1.0, 0.0)).length() < 1e-3);
+    }
+}
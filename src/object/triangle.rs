@@ -1,9 +1,12 @@
 use crate::ray::ray::Ray;
-use crate::ray::vec3::{Vec3, Point3};
+use crate::ray::vec3::{Vec3, Point3, dot};
 use crate::ray::hittable::{Hittable, HitRecord};
 use crate::utils::interval::Interval;
+use crate::utils::aabb::Aabb;
 use std::sync::Arc;
 use crate::object::material::Material;
+use crate::object::light::{Light, LightSample};
+use crate::utils::utils::random_double;
 
 pub struct Triangle {
     pub v0: Point3,
@@ -12,48 +15,150 @@ pub struct Triangle {
     pub material: Arc<dyn Material>,
 }
 
-impl Hittable for Triangle {
-    fn hit(&self, ray: Ray, ray_t: Interval, rec: &mut HitRecord) -> bool {
-        let e1 = self.v1 - self.v0;
-        let e2 = self.v2 - self.v0;
-        let h = ray.direction().cross(&e2);
-        let a = e1.dot(&h);
+// Intersection de Möller-Trumbore ; renvoie t et les coordonnées barycentriques
+// (u, v) du point, qui vaut v0 en (0, 0), v1 en (1, 0) et v2 en (0, 1)
+pub fn intersect_triangle(ray: &Ray, v0: Point3, v1: Point3, v2: Point3, ray_t: Interval) -> Option<(f32, f32, f32)> {
+    let e1 = v1 - v0;
+    let e2 = v2 - v0;
+    let h = ray.direction().cross(&e2);
+    let a = e1.dot(&h);
 
-        if a.abs() < 1e-8 {
-            return false;
-        }
+    // Rayon parallèle au plan ou triangle dégénéré : le seuil de 1e-8 vaut pour
+    // des arêtes unitaires et suit leur longueur, pour les maillages très fins
+    if a.abs() <= 1e-8 * (e1.length_squared() * e2.length_squared()).sqrt() {
+        return None;
+    }
 
-        let f = 1.0 / a;
-        let s = ray.origin() - self.v0;
-        let u = f * s.dot(&h);
+    let f = 1.0 / a;
+    let s = ray.origin() - v0;
+    let u = f * s.dot(&h);
 
-        if u < 0.0 || u > 1.0 {
-            return false;
-        }
+    if !(0.0..=1.0).contains(&u) {
+        return None;
+    }
 
-        let q = s.cross(&e1);
-        let v = f * ray.direction().dot(&q);
+    let q = s.cross(&e1);
+    let v = f * ray.direction().dot(&q);
 
-        if v < 0.0 || u + v > 1.0 {
-            return false;
-        }
+    if v < 0.0 || u + v > 1.0 {
+        return None;
+    }
+
+    let t = f * e2.dot(&q);
+
+    if t < ray_t.min || t > ray_t.max {
+        return None;
+    }
 
-        let t = f * e2.dot(&q);
+    Some((t, u, v))
+}
 
-        if t < ray_t.min || t > ray_t.max {
+impl Hittable for Triangle {
+    fn hit(&self, ray: Ray, ray_t: Interval, rec: &mut HitRecord) -> bool {
+        let Some((t, u, v)) = intersect_triangle(&ray, self.v0, self.v1, self.v2, ray_t) else {
             return false;
-        }
+        };
 
+        let e1 = self.v1 - self.v0;
+        let e2 = self.v2 - self.v0;
         rec.t = Interval::new(t, t);
         rec.p = ray.at(rec.t.min);
         rec.set_face_normal(ray, e1.cross(&e2).unit_vector());
+        rec.u = u;
+        rec.v = v;
         rec.material = Some(self.material.clone());
         true
     }
+
+    fn bounding_box(&self) -> Aabb {
+        let bbox = Aabb::from_points(self.v0, self.v1);
+        Aabb::surrounding(&bbox, &Aabb::from_points(self.v2, self.v2))
+    }
 }
 
 impl Triangle {
     pub fn new(v0: Point3, v1: Point3, v2: Point3, material: Arc<dyn Material>) -> Self {
         Triangle { v0, v1, v2, material }
     }
-} 
\ No newline at end of file
+}
+
+// Échantillonnage uniforme de la surface, converti en densité par angle solide
+impl Light for Triangle {
+    fn sample_li(&self, origin: Point3, time: f32) -> Option<LightSample> {
+        let su = random_double().sqrt();
+        let b1 = random_double() * su;
+        let b0 = 1.0 - su;
+        let point = self.v0 * b0 + self.v1 * b1 + self.v2 * (1.0 - b0 - b1);
+
+        let to_point = point - origin;
+        let distance = to_point.length();
+        if distance < 1e-4 {
+            return None;
+        }
+        let wi = to_point / distance;
+        let normal = (self.v1 - self.v0).cross(&(self.v2 - self.v0));
+        let area = 0.5 * normal.length();
+        let cosine = dot(wi, normal.unit_vector()).abs();
+        if cosine < 1e-6 {
+            return None;
+        }
+
+        let ray = Ray::with_time(origin, wi, time);
+        let mut rec = HitRecord::new();
+        rec.t = Interval::new(distance, distance);
+        rec.p = point;
+        rec.set_face_normal(ray, normal.unit_vector());
+        rec.u = b1;
+        rec.v = 1.0 - b0 - b1;
+        Some(LightSample {
+            wi,
+            distance,
+            radiance: self.material.emitted(&ray, &rec),
+            pdf: distance * distance / (cosine * area),
+        })
+    }
+
+    fn pdf(&self, origin: Point3, direction: Vec3, time: f32) -> f32 {
+        let mut rec = HitRecord::new();
+        if !self.hit(Ray::with_time(origin, direction, time), Interval::new(0.001, f32::INFINITY), &mut rec) {
+            return 0.0;
+        }
+        let normal = (self.v1 - self.v0).cross(&(self.v2 - self.v0));
+        let area = 0.5 * normal.length();
+        let distance = rec.t.min * direction.length();
+        let cosine = dot(direction.unit_vector(), normal.unit_vector()).abs();
+        distance * distance / (cosine * area)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn intersect(scale: f32, direction: Vec3) -> Option<(f32, f32, f32)> {
+        let (v0, v1, v2) = (Point3::new(0.0, 0.0, 0.0), Point3::new(scale, 0.0, 0.0), Point3::new(0.0, scale, 0.0));
+        let ray = Ray::new(Point3::new(0.25 * scale, 0.25 * scale, 1.0), direction);
+        intersect_triangle(&ray, v0, v1, v2, Interval::new(0.001, f32::INFINITY))
+    }
+
+    #[test]
+    fn hit_gives_barycentric_coordinates() {
+        let (t, u, v) = intersect(1.0, Vec3::new(0.0, 0.0, -1.0)).expect("hit");
+        assert!((t - 1.0).abs() < 1e-6 && (u - 0.25).abs() < 1e-6 && (v - 0.25).abs() < 1e-6);
+    }
+
+    #[test]
+    fn tiny_triangles_are_hit() {
+        // Arêtes de 1e-5 : le seuil suit la taille du triangle
+        let (_, u, v) = intersect(1e-5, Vec3::new(0.0, 0.0, -1.0)).expect("hit");
+        assert!((u - 0.25).abs() < 1e-3 && (v - 0.25).abs() < 1e-3);
+    }
+
+    #[test]
+    fn parallel_and_degenerate_miss() {
+        assert!(intersect(1.0, Vec3::new(1.0, 0.0, 0.0)).is_none());
+        let ray = Ray::new(Point3::new(0.5, 0.0, 1.0), Vec3::new(0.0, 0.0, -1.0));
+        let (a, b) = (Point3::new(0.0, 0.0, 0.0), Point3::new(1.0, 0.0, 0.0));
+        assert!(intersect_triangle(&ray, a, b, b * 2.0, Interval::new(0.001, f32::INFINITY)).is_none());
+    }
+}
@@ -0,0 +1,315 @@
+use crate::ray::ray::Ray;
+use crate::ray::vec3::{Vec3, Point3};
+use crate::ray::hittable::{Hittable, HitRecord};
+use crate::utils::interval::Interval;
+use crate::utils::aabb::Aabb;
+use crate::utils::noise::{Noise, Perlin};
+use crate::utils::utils::random_double;
+use crate::object::material::Material;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::sync::Arc;
+
+// Densité variable dans l'espace, en coordonnées du monde
+pub trait DensityField: Send + Sync {
+    fn density(&self, p: Point3) -> f32;
+
+    // Majorant de `density`, qui fixe le pas du suivi delta
+    fn max_density(&self) -> f32;
+
+    // Région où la densité peut être non nulle
+    fn bounding_box(&self) -> Aabb {
+        Aabb::UNIVERSE
+    }
+}
+
+// Grille de voxels posée sur la boîte `bounds`, échantillonnée par
+// interpolation trilinéaire entre les centres des cellules ; nulle en dehors
+pub struct DensityGrid {
+    pub nx: usize,
+    pub ny: usize,
+    pub nz: usize,
+    pub bounds: Aabb,
+    values: Vec<f32>, // x varie le plus vite, puis y, puis z
+    max: f32,
+}
+
+impl DensityGrid {
+    // `None` si le nombre de valeurs ne correspond pas aux dimensions, qui
+    // peuvent venir d'un fichier et dépasser la taille d'un `usize`, ou si une
+    // valeur est négative ou non finie : le majorant du suivi delta serait faux
+    pub fn new(nx: usize, ny: usize, nz: usize, values: Vec<f32>, bounds: Aabb) -> Option<Self> {
+        let count = nx.checked_mul(ny).and_then(|n| n.checked_mul(nz))?;
+        if count == 0 || values.len() != count || !values.iter().all(|v| v.is_finite() && *v >= 0.0) {
+            return None;
+        }
+        let max = values.iter().fold(0.0f32, |m, &v| m.max(v));
+        Some(DensityGrid { nx, ny, nz, bounds, values, max })
+    }
+
+    // Fichier texte : les dimensions `nx ny nz`, puis les nx * ny * nz valeurs ;
+    // `#` commence un commentaire
+    pub fn load_text(path: impl AsRef<Path>, bounds: Aabb) -> io::Result<Self> {
+        let source = fs::read_to_string(path)?;
+        let mut numbers = source
+            .lines()
+            .map(|line| line.split('#').next().unwrap_or(""))
+            .flat_map(str::split_whitespace);
+        let invalid = |message: &str| io::Error::new(io::ErrorKind::InvalidData, message.to_string());
+
+        let mut dimension = || -> io::Result<usize> {
+            numbers.next()
+                .and_then(|word| word.parse::<usize>().ok())
+                .ok_or_else(|| invalid("expected grid dimensions"))
+        };
+        let (nx, ny, nz) = (dimension()?, dimension()?, dimension()?);
+        let values = numbers
+            .map(|word| {
+                word.parse::<f32>()
+                    .ok()
+                    .filter(|v| v.is_finite() && *v >= 0.0)
+                    .ok_or_else(|| invalid("invalid grid value"))
+            })
+            .collect::<io::Result<Vec<f32>>>()?;
+        DensityGrid::new(nx, ny, nz, values, bounds)
+            .ok_or_else(|| invalid("grid value count does not match its dimensions"))
+    }
+
+    // Fichier brut de flottants 32 bits petit-boutistes, sans en-tête
+    pub fn load_raw(path: impl AsRef<Path>, nx: usize, ny: usize, nz: usize, bounds: Aabb) -> io::Result<Self> {
+        let bytes = fs::read(path)?;
+        if bytes.len() % 4 != 0 {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "raw grid size is not a multiple of 4 bytes"));
+        }
+        let values = bytes
+            .chunks_exact(4)
+            .map(|chunk| f32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
+            .collect();
+        DensityGrid::new(nx, ny, nz, values, bounds).ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidData, "raw grid size does not match its dimensions, or a value is negative or not finite")
+        })
+    }
+
+    fn value(&self, x: usize, y: usize, z: usize) -> f32 {
+        self.values[(z * self.ny + y) * self.nx + x]
+    }
+}
+
+impl DensityField for DensityGrid {
+    fn density(&self, p: Point3) -> f32 {
+        let b = &self.bounds;
+        if !(b.x.contains(p.x()) && b.y.contains(p.y()) && b.z.contains(p.z())) {
+            return 0.0;
+        }
+        // Position continue dans la grille, centres des cellules aux demi-entiers
+        let coordinate = |value: f32, axis: Interval, n: usize| {
+            let g = ((value - axis.min) / axis.size() * n as f32 - 0.5).clamp(0.0, (n - 1) as f32);
+            let i = (g as usize).min(n.saturating_sub(2));
+            (i, (i + 1).min(n - 1), g - i as f32)
+        };
+        let (x0, x1, fx) = coordinate(p.x(), b.x, self.nx);
+        let (y0, y1, fy) = coordinate(p.y(), b.y, self.ny);
+        let (z0, z1, fz) = coordinate(p.z(), b.z, self.nz);
+
+        let lerp = |a: f32, b: f32, t: f32| a + (b - a) * t;
+        let plane = |z: usize| {
+            lerp(
+                lerp(self.value(x0, y0, z), self.value(x1, y0, z), fx),
+                lerp(self.value(x0, y1, z), self.value(x1, y1, z), fx),
+                fy,
+            )
+        };
+        lerp(plane(z0), plane(z1), fz).max(0.0)
+    }
+
+    fn max_density(&self) -> f32 {
+        self.max
+    }
+
+    fn bounding_box(&self) -> Aabb {
+        self.bounds
+    }
+}
+
+// Fumée procédurale : fBm de Perlin ramené dans [0, 1]
+pub struct NoiseDensity {
+    pub noise: Perlin,
+    pub frequency: f32,
+    pub octaves: u32,
+}
+
+impl NoiseDensity {
+    pub fn new(seed: u32, frequency: f32, octaves: u32) -> Self {
+        NoiseDensity { noise: Perlin::new(seed), frequency, octaves }
+    }
+}
+
+impl DensityField for NoiseDensity {
+    fn density(&self, p: Point3) -> f32 {
+        (0.5 + self.noise.fbm(p * self.frequency, self.octaves)).clamp(0.0, 1.0)
+    }
+
+    fn max_density(&self) -> f32 {
+        1.0
+    }
+}
+
+// Collision réelle trouvée dans un milieu
+pub struct MediumEvent {
+    pub t: f32,
+    pub emission: Vec3, // lumière émise au point de collision
+    pub phase: Arc<dyn Material>,
+}
+
+// Milieu participant que la caméra traverse segment par segment entre deux surfaces
+pub trait Medium: Send + Sync {
+    // Première collision réelle dans `ray_t`, `None` si le rayon traverse
+    fn sample(&self, ray: Ray, ray_t: Interval) -> Option<MediumEvent>;
+
+    // Fraction de lumière transmise le long de `ray_t`
+    fn transmittance(&self, ray: Ray, ray_t: Interval) -> f32;
+}
+
+// Milieu de densité variable : coefficient d'extinction `scale * density(p)`.
+// Chaque collision émet `emission * emission_field(p)` (le feu) puis diffuse
+// selon `phase`, dont l'albédo colore la lumière diffusée
+pub struct HeterogeneousMedium {
+    pub density: Arc<dyn DensityField>,
+    pub scale: f32,
+    pub phase: Arc<dyn Material>,
+    pub emission: Vec3,
+    pub emission_field: Arc<dyn DensityField>,
+}
+
+impl HeterogeneousMedium {
+    pub fn new(density: Arc<dyn DensityField>, scale: f32, phase: Arc<dyn Material>) -> Self {
+        HeterogeneousMedium {
+            emission_field: density.clone(),
+            density,
+            scale,
+            phase,
+            emission: Vec3::new(0.0, 0.0, 0.0),
+        }
+    }
+
+    // Le champ d'émission vaut la densité par défaut
+    pub fn with_emission(mut self, emission: Vec3, field: Option<Arc<dyn DensityField>>) -> Self {
+        self.emission = emission;
+        if let Some(field) = field {
+            self.emission_field = field;
+        }
+        self
+    }
+
+    // Segment parcouru dans la région utile, avec le majorant en unités de `t`
+    fn segment(&self, ray: Ray, ray_t: Interval) -> Option<(Interval, f32)> {
+        let majorant = self.scale * self.density.max_density() * ray.direction().length();
+        if !majorant.is_finite() || majorant <= 0.0 {
+            return None;
+        }
+        let span = self.density.bounding_box().clip(&ray, ray_t)?;
+        // Un rayon sans fin dans un milieu infini n'est pas suivi
+        if !span.max.is_finite() || !span.min.is_finite() {
+            return None;
+        }
+        Some((span, majorant))
+    }
+}
+
+impl Medium for HeterogeneousMedium {
+    // Suivi delta : collisions tirées avec le majorant, acceptées en proportion
+    // de la densité locale
+    fn sample(&self, ray: Ray, ray_t: Interval) -> Option<MediumEvent> {
+        let (span, majorant) = self.segment(ray, ray_t)?;
+        let mut t = span.min;
+        loop {
+            t -= (1.0 - random_double()).ln() / majorant;
+            if t >= span.max {
+                return None;
+            }
+            let p = ray.at(t);
+            if random_double() * self.density.max_density() < self.density.density(p) {
+                let emission = self.emission * self.emission_field.density(p);
+                return Some(MediumEvent { t, emission, phase: self.phase.clone() });
+            }
+        }
+    }
+
+    // Suivi de rapport : chaque collision fictive multiplie par la part de vide
+    fn transmittance(&self, ray: Ray, ray_t: Interval) -> f32 {
+        let Some((span, majorant)) = self.segment(ray, ray_t) else {
+            return 1.0;
+        };
+        let max_density = self.density.max_density();
+        let mut transmittance = 1.0;
+        let mut t = span.min;
+        loop {
+            t -= (1.0 - random_double()).ln() / majorant;
+            if t >= span.max {
+                return transmittance;
+            }
+            transmittance *= 1.0 - self.density.density(ray.at(t)) / max_density;
+            // Roulette russe une fois la transmittance devenue négligeable
+            if transmittance < 0.1 {
+                if random_double() < 0.5 {
+                    return 0.0;
+                }
+                transmittance *= 2.0;
+            }
+        }
+    }
+}
+
+// Contour d'un milieu : la caméra y entre et en sort en traversant la surface.
+// Si `surface` est faux, le contour est invisible ; sinon il garde le matériau
+// de `boundary` (du verre rempli de fumée)
+pub struct MediumBoundary {
+    pub boundary: Box<dyn Hittable>,
+    pub medium: Arc<dyn Medium>,
+    pub surface: bool,
+}
+
+impl MediumBoundary {
+    pub fn new(boundary: Box<dyn Hittable>, medium: Arc<dyn Medium>, surface: bool) -> Self {
+        MediumBoundary { boundary, medium, surface }
+    }
+}
+
+impl Hittable for MediumBoundary {
+    fn hit(&self, ray: Ray, ray_t: Interval, rec: &mut HitRecord) -> bool {
+        if !self.boundary.hit(ray, ray_t, rec) {
+            return false;
+        }
+        if !self.surface {
+            rec.material = None;
+        }
+        rec.medium = Some(self.medium.clone());
+        true
+    }
+
+    fn bounding_box(&self) -> Aabb {
+        self.boundary.bounding_box()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid(values: Vec<f32>) -> Option<DensityGrid> {
+        let bounds = Aabb::from_points(Point3::new(0.0, 0.0, 0.0), Point3::new(1.0, 1.0, 1.0));
+        DensityGrid::new(2, 1, 1, values, bounds)
+    }
+
+    #[test]
+    fn grid_values_must_be_finite_and_non_negative() {
+        assert_eq!(grid(vec![0.0, 2.5]).map(|g| g.max_density()), Some(2.5));
+        assert!(grid(vec![1.0]).is_none());
+        assert!(grid(vec![1.0, -0.5]).is_none());
+        assert!(grid(vec![1.0, f32::INFINITY]).is_none());
+        assert!(grid(vec![f32::NAN, 1.0]).is_none());
+        let bounds = Aabb::from_points(Point3::new(0.0, 0.0, 0.0), Point3::new(1.0, 1.0, 1.0));
+        assert!(DensityGrid::new(usize::MAX, 2, 1, vec![1.0], bounds).is_none());
+    }
+}
@@ -0,0 +1,77 @@
+// OpenEXR minimal : une seule partie en lignes, sans compression, canaux
+// R, G et B en flottants 32 bits. Toutes les valeurs sont en petit-boutiste.
+
+use crate::utils::image::Image;
+use std::io::{self, Write};
+
+const MAGIC: [u8; 4] = [0x76, 0x2f, 0x31, 0x01];
+const PIXEL_TYPE_FLOAT: i32 = 2;
+
+pub fn write_exr(out: &mut impl Write, image: &Image) -> io::Result<()> {
+    if image.width() == 0 || image.height() == 0 {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, "cannot write an empty EXR image"));
+    }
+    let (width, height) = (image.width(), image.height());
+    let window = [0, 0, width as i32 - 1, height as i32 - 1];
+
+    let mut header = Vec::new();
+    header.extend_from_slice(&MAGIC);
+    header.extend_from_slice(&2u32.to_le_bytes());
+
+    // Les canaux sont rangés par ordre alphabétique
+    let mut channels = Vec::new();
+    for name in ["B", "G", "R"] {
+        channels.extend_from_slice(name.as_bytes());
+        channels.push(0);
+        channels.extend_from_slice(&PIXEL_TYPE_FLOAT.to_le_bytes());
+        // pLinear et trois octets réservés, puis l'échantillonnage x et y
+        channels.extend_from_slice(&[0, 0, 0, 0]);
+        channels.extend_from_slice(&1i32.to_le_bytes());
+        channels.extend_from_slice(&1i32.to_le_bytes());
+    }
+    channels.push(0);
+    attribute(&mut header, "channels", "chlist", &channels);
+    attribute(&mut header, "compression", "compression", &[0]);
+    attribute(&mut header, "dataWindow", "box2i", &i32_bytes(&window));
+    attribute(&mut header, "displayWindow", "box2i", &i32_bytes(&window));
+    attribute(&mut header, "lineOrder", "lineOrder", &[0]);
+    attribute(&mut header, "pixelAspectRatio", "float", &1f32.to_le_bytes());
+    attribute(&mut header, "screenWindowCenter", "v2f", &[0; 8]);
+    attribute(&mut header, "screenWindowWidth", "float", &1f32.to_le_bytes());
+    header.push(0);
+
+    // Table des positions absolues de chaque ligne dans le fichier
+    let line_size = 8 + width * 3 * 4;
+    let first_line = header.len() + height * 8;
+    for y in 0..height {
+        header.extend_from_slice(&((first_line + y * line_size) as u64).to_le_bytes());
+    }
+    out.write_all(&header)?;
+
+    let mut line = Vec::with_capacity(line_size);
+    for y in 0..height {
+        line.clear();
+        line.extend_from_slice(&(y as i32).to_le_bytes());
+        line.extend_from_slice(&((width * 3 * 4) as i32).to_le_bytes());
+        for channel in [2, 1, 0] {
+            for x in 0..width {
+                line.extend_from_slice(&image.get(x, y)[channel].to_le_bytes());
+            }
+        }
+        out.write_all(&line)?;
+    }
+    Ok(())
+}
+
+fn attribute(header: &mut Vec<u8>, name: &str, kind: &str, value: &[u8]) {
+    header.extend_from_slice(name.as_bytes());
+    header.push(0);
+    header.extend_from_slice(kind.as_bytes());
+    header.push(0);
+    header.extend_from_slice(&(value.len() as i32).to_le_bytes());
+    header.extend_from_slice(value);
+}
+
+fn i32_bytes(values: &[i32]) -> Vec<u8> {
+    values.iter().flat_map(|v| v.to_le_bytes()).collect()
+}
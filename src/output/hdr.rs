@@ -0,0 +1,24 @@
+use crate::utils::image::Image;
+use std::io::{self, Write};
+
+// Radiance RGBE (.hdr), lignes non compressées de haut en bas
+pub fn write_hdr(out: &mut impl Write, image: &Image) -> io::Result<()> {
+    write!(out, "#?RADIANCE\nFORMAT=32-bit_rle_rgbe\n\n-Y {} +X {}\n", image.height(), image.width())?;
+    let mut data = Vec::with_capacity(image.pixels().len() * 4);
+    for pixel in image.pixels() {
+        data.extend_from_slice(&to_rgbe(pixel.x(), pixel.y(), pixel.z()));
+    }
+    out.write_all(&data)
+}
+
+// Mantisses sur 8 bits partageant l'exposant de la plus grande composante
+fn to_rgbe(r: f32, g: f32, b: f32) -> [u8; 4] {
+    let (r, g, b) = (r.max(0.0), g.max(0.0), b.max(0.0));
+    let v = r.max(g).max(b);
+    if !v.is_finite() || v < 1e-32 {
+        return [0; 4];
+    }
+    let exponent = v.log2().floor() as i32 + 1;
+    let scale = 256.0 / 2f32.powi(exponent);
+    [(r * scale) as u8, (g * scale) as u8, (b * scale) as u8, (exponent + 128) as u8]
+}
@@ -0,0 +1,117 @@
+pub mod ppm;
+pub mod png;
+pub mod hdr;
+pub mod pfm;
+pub mod exr;
+
+use crate::ray::color::DisplayTransform;
+use crate::utils::image::Image;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ImageFormat {
+    Ppm,
+    Png,
+    // Formats à virgule flottante : radiance linéaire, sans correction ni écrêtage
+    Hdr,
+    Pfm,
+    Exr,
+}
+
+// Profondeur de chaque composante dans le fichier
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BitDepth {
+    Eight,
+    Sixteen,
+}
+
+// Réglages des formats affichables, ignorés par les formats à virgule flottante
+#[derive(Clone, Copy, Debug)]
+pub struct Encoding {
+    pub depth: BitDepth,
+    pub display: DisplayTransform,
+}
+
+impl Default for Encoding {
+    fn default() -> Self {
+        Encoding { depth: BitDepth::Eight, display: DisplayTransform::default() }
+    }
+}
+
+impl ImageFormat {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "ppm" => Some(ImageFormat::Ppm),
+            "png" => Some(ImageFormat::Png),
+            "hdr" | "rgbe" => Some(ImageFormat::Hdr),
+            "pfm" => Some(ImageFormat::Pfm),
+            "exr" => Some(ImageFormat::Exr),
+            _ => None,
+        }
+    }
+
+    // Format déduit de l'extension du fichier
+    pub fn from_path(path: impl AsRef<Path>) -> Option<Self> {
+        path.as_ref().extension().and_then(|e| e.to_str()).and_then(ImageFormat::from_name)
+    }
+
+    pub fn is_hdr(self) -> bool {
+        matches!(self, ImageFormat::Hdr | ImageFormat::Pfm | ImageFormat::Exr)
+    }
+}
+
+impl BitDepth {
+    pub fn from_bits(bits: u32) -> Option<Self> {
+        match bits {
+            8 => Some(BitDepth::Eight),
+            16 => Some(BitDepth::Sixteen),
+            _ => None,
+        }
+    }
+
+    pub fn bits(self) -> u32 {
+        match self {
+            BitDepth::Eight => 8,
+            BitDepth::Sixteen => 16,
+        }
+    }
+}
+
+pub fn write_image(out: &mut impl Write, image: &Image, format: ImageFormat, encoding: Encoding) -> io::Result<()> {
+    match format {
+        ImageFormat::Ppm => ppm::write_ppm(out, image, encoding),
+        ImageFormat::Png => png::write_png(out, image, encoding),
+        ImageFormat::Hdr => hdr::write_hdr(out, image),
+        ImageFormat::Pfm => pfm::write_pfm(out, image),
+        ImageFormat::Exr => exr::write_exr(out, image),
+    }
+}
+
+// Écrit l'image dans `path`, au format déduit de son extension
+pub fn save_image(path: impl AsRef<Path>, image: &Image, encoding: Encoding) -> io::Result<()> {
+    let path = path.as_ref();
+    let format = ImageFormat::from_path(path).ok_or_else(|| {
+        io::Error::new(io::ErrorKind::InvalidInput, format!("unknown image format for {}", path.display()))
+    })?;
+    save_image_as(path, image, format, encoding)
+}
+
+pub fn save_image_as(path: impl AsRef<Path>, image: &Image, format: ImageFormat, encoding: Encoding) -> io::Result<()> {
+    let mut out = BufWriter::new(File::create(path)?);
+    write_image(&mut out, image, format, encoding)?;
+    out.flush()
+}
+
+// Composantes RVB prêtes à afficher, ligne par ligne, en gros-boutiste sur 16 bits
+pub(crate) fn display_samples(image: &Image, encoding: Encoding) -> Vec<u8> {
+    let display = encoding.display;
+    match encoding.depth {
+        BitDepth::Eight => image.pixels().iter().flat_map(|&c| display.to_rgb8(c)).collect(),
+        BitDepth::Sixteen => image.pixels().iter()
+            .flat_map(|&c| display.to_rgb16(c))
+            .flat_map(|x| x.to_be_bytes())
+            .collect(),
+    }
+}
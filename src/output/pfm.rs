@@ -0,0 +1,17 @@
+use crate::utils::image::Image;
+use std::io::{self, Write};
+
+// Portable Float Map en petit-boutiste (échelle négative), lignes de bas en haut
+pub fn write_pfm(out: &mut impl Write, image: &Image) -> io::Result<()> {
+    write!(out, "PF\n{} {}\n-1.0\n", image.width(), image.height())?;
+    let mut data = Vec::with_capacity(image.pixels().len() * 12);
+    for y in (0..image.height()).rev() {
+        for x in 0..image.width() {
+            let pixel = image.get(x, y);
+            for channel in [pixel.x(), pixel.y(), pixel.z()] {
+                data.extend_from_slice(&channel.to_le_bytes());
+            }
+        }
+    }
+    out.write_all(&data)
+}
@@ -0,0 +1,428 @@
+// Encodeur PNG autonome : filtres par ligne, compression deflate à codes de
+// Huffman fixes avec recherche LZ77 par table de hachage, enveloppe zlib.
+
+use crate::output::{Encoding, display_samples};
+use crate::utils::image::Image;
+use std::io::{self, Write};
+
+const SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+
+// Image RVB sans transparence, 8 ou 16 bits par composante
+pub fn write_png(out: &mut impl Write, image: &Image, encoding: Encoding) -> io::Result<()> {
+    if image.width() == 0 || image.height() == 0 {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, "cannot write an empty PNG image"));
+    }
+    let depth = encoding.depth;
+    let bytes_per_pixel = 3 * depth.bits() as usize / 8;
+    let samples = display_samples(image, encoding);
+    let filtered = filter_scanlines(&samples, image.width() * bytes_per_pixel, bytes_per_pixel);
+
+    let mut header = Vec::with_capacity(13);
+    header.extend_from_slice(&(image.width() as u32).to_be_bytes());
+    header.extend_from_slice(&(image.height() as u32).to_be_bytes());
+    // Profondeur, couleur RVB (2), compression, filtrage, pas d'entrelacement
+    header.extend_from_slice(&[depth.bits() as u8, 2, 0, 0, 0]);
+
+    out.write_all(&SIGNATURE)?;
+    write_chunk(out, b"IHDR", &header)?;
+    write_chunk(out, b"IDAT", &zlib_compress(&filtered))?;
+    write_chunk(out, b"IEND", &[])
+}
+
+fn write_chunk(out: &mut impl Write, kind: &[u8; 4], data: &[u8]) -> io::Result<()> {
+    out.write_all(&(data.len() as u32).to_be_bytes())?;
+    out.write_all(kind)?;
+    out.write_all(data)?;
+    let crc = crc32_update(crc32_update(0xffff_ffff, kind), data) ^ 0xffff_ffff;
+    out.write_all(&crc.to_be_bytes())
+}
+
+const CRC_TABLE: [u32; 256] = crc_table();
+
+const fn crc_table() -> [u32; 256] {
+    let mut table = [0; 256];
+    let mut n = 0;
+    while n < 256 {
+        let mut c = n as u32;
+        let mut k = 0;
+        while k < 8 {
+            c = if c & 1 != 0 { 0xedb8_8320 ^ (c >> 1) } else { c >> 1 };
+            k += 1;
+        }
+        table[n] = c;
+        n += 1;
+    }
+    table
+}
+
+fn crc32_update(crc: u32, data: &[u8]) -> u32 {
+    data.iter().fold(crc, |c, &byte| CRC_TABLE[((c ^ byte as u32) & 0xff) as usize] ^ (c >> 8))
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    const MOD: u32 = 65521;
+    let (mut a, mut b) = (1u32, 0u32);
+    // 5552 octets au plus avant que `b` ne puisse déborder
+    for chunk in data.chunks(5552) {
+        for &byte in chunk {
+            a += byte as u32;
+            b += a;
+        }
+        a %= MOD;
+        b %= MOD;
+    }
+    (b << 16) | a
+}
+
+// Choisit pour chaque ligne le filtre qui minimise la somme des écarts signés
+fn filter_scanlines(samples: &[u8], stride: usize, bytes_per_pixel: usize) -> Vec<u8> {
+    let rows = samples.len() / stride;
+    let mut out = Vec::with_capacity((stride + 1) * rows);
+    let zero = vec![0u8; stride];
+    let mut candidate = vec![0u8; stride];
+    let mut best = vec![0u8; stride];
+
+    for (y, row) in samples.chunks_exact(stride).enumerate() {
+        let prior = if y == 0 { &zero[..] } else { &samples[(y - 1) * stride..y * stride] };
+        let mut best_filter = 0;
+        let mut best_cost = u64::MAX;
+        for filter in 0..5u8 {
+            for i in 0..stride {
+                let a = if i >= bytes_per_pixel { row[i - bytes_per_pixel] } else { 0 };
+                let b = prior[i];
+                let c = if i >= bytes_per_pixel { prior[i - bytes_per_pixel] } else { 0 };
+                let predicted = match filter {
+                    0 => 0,
+                    1 => a,
+                    2 => b,
+                    3 => ((a as u16 + b as u16) / 2) as u8,
+                    _ => paeth(a, b, c),
+                };
+                candidate[i] = row[i].wrapping_sub(predicted);
+            }
+            let cost: u64 = candidate.iter().map(|&v| (v as i8).unsigned_abs() as u64).sum();
+            if cost < best_cost {
+                best_cost = cost;
+                best_filter = filter;
+                std::mem::swap(&mut best, &mut candidate);
+            }
+        }
+        out.push(best_filter);
+        out.extend_from_slice(&best);
+    }
+    out
+}
+
+fn paeth(a: u8, b: u8, c: u8) -> u8 {
+    let p = a as i16 + b as i16 - c as i16;
+    let pa = (p - a as i16).abs();
+    let pb = (p - b as i16).abs();
+    let pc = (p - c as i16).abs();
+    if pa <= pb && pa <= pc {
+        a
+    } else if pb <= pc {
+        b
+    } else {
+        c
+    }
+}
+
+fn zlib_compress(data: &[u8]) -> Vec<u8> {
+    // Méthode deflate, fenêtre de 32 Ko, sans dictionnaire
+    let mut out = vec![0x78, 0x01];
+    out.extend(deflate(data));
+    out.extend_from_slice(&adler32(data).to_be_bytes());
+    out
+}
+
+const WINDOW_SIZE: usize = 32768;
+const MIN_MATCH: usize = 3;
+const MAX_MATCH: usize = 258;
+const HASH_BITS: u32 = 15;
+const MAX_CHAIN: usize = 64;
+const NO_POSITION: usize = usize::MAX;
+
+const LENGTH_BASE: [u16; 29] = [
+    3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31,
+    35, 43, 51, 59, 67, 83, 99, 115, 131, 163, 195, 227, 258,
+];
+const LENGTH_EXTRA: [u8; 29] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2,
+    3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0,
+];
+const DISTANCE_BASE: [u16; 30] = [
+    1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193,
+    257, 385, 513, 769, 1025, 1537, 2049, 3073, 4097, 6145, 8193, 12289, 16385, 24577,
+];
+const DISTANCE_EXTRA: [u8; 30] = [
+    0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6,
+    7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13, 13,
+];
+
+// Un seul bloc final compressé avec les codes de Huffman fixes
+fn deflate(data: &[u8]) -> Vec<u8> {
+    let mut bits = BitWriter::default();
+    bits.write(1, 1);
+    bits.write(1, 2);
+
+    let mut head = vec![NO_POSITION; 1 << HASH_BITS];
+    let mut prev = vec![NO_POSITION; WINDOW_SIZE];
+    let insert = |pos: usize, head: &mut Vec<usize>, prev: &mut Vec<usize>| {
+        if pos + MIN_MATCH <= data.len() {
+            let h = hash(&data[pos..]);
+            prev[pos % WINDOW_SIZE] = head[h];
+            head[h] = pos;
+        }
+    };
+
+    let mut pos = 0;
+    while pos < data.len() {
+        let (length, distance) = longest_match(data, pos, &head, &prev);
+        if length >= MIN_MATCH {
+            write_length(&mut bits, length);
+            write_distance(&mut bits, distance);
+            for p in pos..pos + length {
+                insert(p, &mut head, &mut prev);
+            }
+            pos += length;
+        } else {
+            write_literal(&mut bits, data[pos] as u32);
+            insert(pos, &mut head, &mut prev);
+            pos += 1;
+        }
+    }
+    write_literal(&mut bits, 256);
+    bits.finish()
+}
+
+fn hash(bytes: &[u8]) -> usize {
+    let h = ((bytes[0] as u32) << 10) ^ ((bytes[1] as u32) << 5) ^ bytes[2] as u32;
+    (h & ((1 << HASH_BITS) - 1)) as usize
+}
+
+// Plus longue correspondance dans la fenêtre, en suivant la chaîne de hachage
+fn longest_match(data: &[u8], pos: usize, head: &[usize], prev: &[usize]) -> (usize, usize) {
+    let max_length = MAX_MATCH.min(data.len() - pos);
+    if max_length < MIN_MATCH {
+        return (0, 0);
+    }
+    let mut best = (0, 0);
+    let mut candidate = head[hash(&data[pos..])];
+    for _ in 0..MAX_CHAIN {
+        if candidate == NO_POSITION || candidate >= pos || pos - candidate > WINDOW_SIZE {
+            break;
+        }
+        let length = data[candidate..candidate + max_length].iter()
+            .zip(&data[pos..pos + max_length])
+            .take_while(|(a, b)| a == b)
+            .count();
+        if length > best.0 {
+            best = (length, pos - candidate);
+            if length == max_length {
+                break;
+            }
+        }
+        // Une entrée écrasée par une position plus récente termine la chaîne
+        let next = prev[candidate % WINDOW_SIZE];
+        if next == NO_POSITION || next >= candidate {
+            break;
+        }
+        candidate = next;
+    }
+    best
+}
+
+fn write_literal(bits: &mut BitWriter, symbol: u32) {
+    match symbol {
+        0..=143 => bits.write_code(0x30 + symbol, 8),
+        144..=255 => bits.write_code(0x190 + symbol - 144, 9),
+        256..=279 => bits.write_code(symbol - 256, 7),
+        _ => bits.write_code(0xc0 + symbol - 280, 8),
+    }
+}
+
+fn write_length(bits: &mut BitWriter, length: usize) {
+    let code = LENGTH_BASE.iter().rposition(|&base| base as usize <= length).unwrap_or(0);
+    write_literal(bits, 257 + code as u32);
+    bits.write((length - LENGTH_BASE[code] as usize) as u32, LENGTH_EXTRA[code] as u32);
+}
+
+fn write_distance(bits: &mut BitWriter, distance: usize) {
+    let code = DISTANCE_BASE.iter().rposition(|&base| base as usize <= distance).unwrap_or(0);
+    bits.write_code(code as u32, 5);
+    bits.write((distance - DISTANCE_BASE[code] as usize) as u32, DISTANCE_EXTRA[code] as u32);
+}
+
+// Flux de bits deflate, rempli à partir du bit de poids faible
+#[derive(Default)]
+struct BitWriter {
+    bytes: Vec<u8>,
+    buffer: u64,
+    count: u32,
+}
+
+impl BitWriter {
+    fn write(&mut self, value: u32, count: u32) {
+        self.buffer |= (value as u64) << self.count;
+        self.count += count;
+        while self.count >= 8 {
+            self.bytes.push(self.buffer as u8);
+            self.buffer >>= 8;
+            self.count -= 8;
+        }
+    }
+
+    // Les codes de Huffman s'écrivent en commençant par le bit de poids fort
+    fn write_code(&mut self, code: u32, length: u32) {
+        self.write(code.reverse_bits() >> (32 - length), length);
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.count > 0 {
+            self.bytes.push(self.buffer as u8);
+        }
+        self.bytes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Décodeur minimal d'un bloc deflate à codes fixes, pour vérifier l'encodeur
+    struct BitReader<'a> {
+        bytes: &'a [u8],
+        position: usize,
+    }
+
+    impl BitReader<'_> {
+        fn bit(&mut self) -> u32 {
+            let bit = (self.bytes[self.position / 8] >> (self.position % 8)) & 1;
+            self.position += 1;
+            bit as u32
+        }
+
+        fn bits(&mut self, count: u32) -> u32 {
+            (0..count).fold(0, |value, i| value | (self.bit() << i))
+        }
+
+        fn code(&mut self, length: u32) -> u32 {
+            (0..length).fold(0, |code, _| (code << 1) | self.bit())
+        }
+
+        fn literal(&mut self) -> u32 {
+            let code = self.code(7);
+            if code <= 0x17 {
+                return 256 + code;
+            }
+            let code = (code << 1) | self.bit();
+            match code {
+                0x30..=0xbf => code - 0x30,
+                0xc0..=0xc7 => 280 + code - 0xc0,
+                _ => 144 + ((code << 1) | self.bit()) - 0x190,
+            }
+        }
+    }
+
+    fn inflate(bytes: &[u8]) -> Vec<u8> {
+        let mut reader = BitReader { bytes, position: 0 };
+        assert_eq!(reader.bits(1), 1, "final block");
+        assert_eq!(reader.bits(2), 1, "fixed Huffman codes");
+        let mut out = Vec::new();
+        loop {
+            let symbol = reader.literal();
+            match symbol {
+                0..=255 => out.push(symbol as u8),
+                256 => return out,
+                _ => {
+                    let code = (symbol - 257) as usize;
+                    let length = LENGTH_BASE[code] as usize + reader.bits(LENGTH_EXTRA[code] as u32) as usize;
+                    let code = reader.code(5) as usize;
+                    let distance = DISTANCE_BASE[code] as usize + reader.bits(DISTANCE_EXTRA[code] as u32) as usize;
+                    // Copie octet par octet : la source peut chevaucher la destination
+                    for _ in 0..length {
+                        out.push(out[out.len() - distance]);
+                    }
+                }
+            }
+        }
+    }
+
+    fn unfilter(filtered: &[u8], stride: usize, bytes_per_pixel: usize) -> Vec<u8> {
+        let mut out: Vec<u8> = Vec::new();
+        for (y, line) in filtered.chunks_exact(stride + 1).enumerate() {
+            for i in 0..stride {
+                let a = if i >= bytes_per_pixel { out[y * stride + i - bytes_per_pixel] } else { 0 };
+                let b = if y > 0 { out[(y - 1) * stride + i] } else { 0 };
+                let c = if y > 0 && i >= bytes_per_pixel { out[(y - 1) * stride + i - bytes_per_pixel] } else { 0 };
+                let predicted = match line[0] {
+                    0 => 0,
+                    1 => a,
+                    2 => b,
+                    3 => ((a as u16 + b as u16) / 2) as u8,
+                    _ => paeth(a, b, c),
+                };
+                out.push(line[1 + i].wrapping_add(predicted));
+            }
+        }
+        out
+    }
+
+    #[test]
+    fn deflate_known_streams() {
+        // Mêmes octets que zlib au niveau des codes fixes
+        assert_eq!(deflate(b"a"), [0x4b, 0x04, 0x00]);
+        // 'a', 'b', puis longueur 4 à distance 2 qui recouvre sa propre sortie
+        assert_eq!(deflate(b"ababab"), [0x4b, 0x4c, 0x02, 0x41, 0x00]);
+        assert_eq!(inflate(&[0x4b, 0x4c, 0x02, 0x41, 0x00]), b"ababab");
+        assert_eq!(inflate(&deflate(b"")), b"");
+    }
+
+    #[test]
+    fn deflate_round_trip() {
+        let mut data = Vec::new();
+        let mut state = 12345u32;
+        for i in 0..5000u32 {
+            state = state.wrapping_mul(1_103_515_245).wrapping_add(12345);
+            // Alternance de bruit et de motifs répétés pour exercer les correspondances
+            data.push(if i % 700 < 350 { (state >> 16) as u8 } else { (i % 7) as u8 * 40 });
+        }
+        data.resize(data.len() + 1000, 0xff);
+        let compressed = deflate(&data);
+        assert_eq!(inflate(&compressed), data);
+        assert!(compressed.len() < data.len());
+    }
+
+    #[test]
+    fn zlib_wrapper() {
+        assert_eq!(zlib_compress(b"a"), [0x78, 0x01, 0x4b, 0x04, 0x00, 0x00, 0x62, 0x00, 0x62]);
+    }
+
+    #[test]
+    fn checksums() {
+        assert_eq!(adler32(b""), 1);
+        assert_eq!(adler32(b"Wikipedia"), 0x11e6_0398);
+        assert_eq!(crc32_update(0xffff_ffff, b"123456789") ^ 0xffff_ffff, 0xcbf4_3926);
+        assert_eq!(crc32_update(0xffff_ffff, b"IEND") ^ 0xffff_ffff, 0xae42_6082);
+        // Adler-32 sur plusieurs blocs de 5552 octets
+        let long = vec![0xffu8; 20000];
+        let (a, b) = long.iter().fold((1u64, 0u64), |(a, b), &byte| {
+            let a = (a + byte as u64) % 65521;
+            (a, (b + a) % 65521)
+        });
+        assert_eq!(adler32(&long), ((b << 16) | a) as u32);
+    }
+
+    #[test]
+    fn filtered_scanlines_decode() {
+        let (width, height, bytes_per_pixel) = (7, 5, 3);
+        let stride = width * bytes_per_pixel;
+        let samples: Vec<u8> = (0..stride * height)
+            .map(|i| ((i % stride) * 9 + (i / stride) * 31 + (i * i) % 13) as u8)
+            .collect();
+        let filtered = filter_scanlines(&samples, stride, bytes_per_pixel);
+        assert_eq!(filtered.len(), (stride + 1) * height);
+        assert!(filtered.chunks_exact(stride + 1).all(|line| line[0] <= 4));
+        assert_eq!(unfilter(&filtered, stride, bytes_per_pixel), samples);
+    }
+}
@@ -0,0 +1,13 @@
+use crate::output::{BitDepth, Encoding, display_samples};
+use crate::utils::image::Image;
+use std::io::{self, Write};
+
+// PPM binaire (P6), 255 ou 65535 niveaux par composante
+pub fn write_ppm(out: &mut impl Write, image: &Image, encoding: Encoding) -> io::Result<()> {
+    let max_value = match encoding.depth {
+        BitDepth::Eight => 255,
+        BitDepth::Sixteen => 65535,
+    };
+    write!(out, "P6\n{} {}\n{}\n", image.width(), image.height(), max_value)?;
+    out.write_all(&display_samples(image, encoding))
+}
@@ -1,23 +1,87 @@
 use crate::ray::vec3::Vec3;
-use crate::utils::interval::Interval;
 
-pub fn linear_to_gamma(x :f32) -> f32 {
-    if x > 0.0 {
-        x.sqrt()
+// Fonction de transfert sRGB (IEC 61966-2-1), composante linéaire vers encodée
+pub fn linear_to_srgb(x: f32) -> f32 {
+    if x <= 0.0 {
+        0.0
+    } else if x <= 0.003_130_8 {
+        12.92 * x
     } else {
+        1.055 * x.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+pub fn srgb_to_linear(x: f32) -> f32 {
+    if x <= 0.0 {
         0.0
+    } else if x <= 0.040_45 {
+        x / 12.92
+    } else {
+        ((x + 0.055) / 1.055).powf(2.4)
     }
 }
 
-pub fn write_color(pixel_color: Vec3, samples_per_pixel: i32) {
-    let scale = 1.0 / samples_per_pixel as f32;
-    let r = (pixel_color.x() * scale).sqrt();
-    let g = (pixel_color.y() * scale).sqrt();
-    let b = (pixel_color.z() * scale).sqrt();
-
-    println!("{} {} {}", 
-        (256.0 * r.clamp(0.0, 0.999)) as i32,
-        (256.0 * g.clamp(0.0, 0.999)) as i32,
-        (256.0 * b.clamp(0.0, 0.999)) as i32
-    );
-}
\ No newline at end of file
+// Compression de la radiance vers [0, 1] avant l'encodage sRGB
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ToneMapper {
+    Clamp,
+    Reinhard,
+    Aces,
+}
+
+impl ToneMapper {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "clamp" | "none" => Some(ToneMapper::Clamp),
+            "reinhard" => Some(ToneMapper::Reinhard),
+            "aces" | "filmic" => Some(ToneMapper::Aces),
+            _ => None,
+        }
+    }
+
+    pub fn map(self, x: f32) -> f32 {
+        let x = x.max(0.0);
+        let mapped = match self {
+            ToneMapper::Clamp => x,
+            ToneMapper::Reinhard => x / (1.0 + x),
+            // Approximation de la courbe filmique ACES par Krzysztof Narkowicz
+            ToneMapper::Aces => (x * (2.51 * x + 0.03)) / (x * (2.43 * x + 0.59) + 0.14),
+        };
+        mapped.clamp(0.0, 1.0)
+    }
+}
+
+// Passage de la radiance linéaire aux valeurs affichées, partagé par l'aperçu et
+// les fichiers : exposition en diaphragmes, compression, puis encodage sRGB
+#[derive(Clone, Copy, Debug)]
+pub struct DisplayTransform {
+    pub exposure: f32,
+    pub tone_mapper: ToneMapper,
+}
+
+impl Default for DisplayTransform {
+    fn default() -> Self {
+        Self::new(0.0, ToneMapper::Clamp)
+    }
+}
+
+impl DisplayTransform {
+    pub fn new(exposure: f32, tone_mapper: ToneMapper) -> Self {
+        DisplayTransform { exposure, tone_mapper }
+    }
+
+    // Composante encodée dans [0, 1] ; NaN devient noir
+    pub fn apply(&self, x: f32) -> f32 {
+        let exposed = x * 2f32.powf(self.exposure);
+        let exposed = if exposed.is_nan() { 0.0 } else { exposed };
+        linear_to_srgb(self.tone_mapper.map(exposed))
+    }
+
+    pub fn to_rgb8(&self, color: Vec3) -> [u8; 3] {
+        [color.x(), color.y(), color.z()].map(|x| (self.apply(x) * 255.0).round() as u8)
+    }
+
+    pub fn to_rgb16(&self, color: Vec3) -> [u16; 3] {
+        [color.x(), color.y(), color.z()].map(|x| (self.apply(x) * 65535.0).round() as u16)
+    }
+}
@@ -2,11 +2,71 @@ use crate::ray::ray::Ray;
 use crate::ray::vec3::{Vec3, Point3};
 use crate::ray::vec3::dot;
 use crate::utils::interval::Interval;
+use crate::utils::aabb::Aabb;
 use crate::object::material::Material;
+use crate::object::volume::Medium;
 use std::sync::Arc;
 
-pub trait Hittable {
+pub trait Hittable: Send + Sync {
     fn hit(&self, ray: Ray, ray_t: Interval, rec: &mut HitRecord) -> bool;
+    fn bounding_box(&self) -> Aabb;
+
+    // Test d'occultation pour les rayons d'ombre : n'importe quelle intersection suffit
+    fn occluded(&self, ray: Ray, ray_t: Interval) -> bool {
+        self.hit(ray, ray_t, &mut HitRecord::new())
+    }
+
+    // Toutes les traversées de la surface dans `ray_t`, ajoutées à `hits` par t
+    // croissant ; `front_face` indique une entrée dans le solide. Par défaut,
+    // `hit` est relancé juste après chaque intersection trouvée
+    fn intersections(&self, ray: Ray, ray_t: Interval, hits: &mut Vec<HitRecord>) {
+        let step = 1e-4 / ray.direction().length();
+        let mut t_min = ray_t.min;
+        loop {
+            let mut rec = HitRecord::new();
+            if !self.hit(ray, Interval::new(t_min, ray_t.max), &mut rec) {
+                return;
+            }
+            t_min = rec.t.min + step.max(rec.t.min.abs() * 1e-6);
+            hits.push(rec);
+        }
+    }
+
+    // Portions de la droite du rayon à l'intérieur du solide, triées ; n'a de
+    // sens que pour une surface fermée
+    fn spans(&self, ray: Ray) -> Vec<Span> {
+        let mut hits = Vec::new();
+        self.intersections(ray, Interval::UNIVERSE, &mut hits);
+        let mut spans = Vec::new();
+        let mut enter: Option<HitRecord> = None;
+        for rec in hits {
+            if rec.front_face {
+                enter.get_or_insert(rec);
+            } else if let Some(enter) = enter.take() {
+                spans.push(Span::new(enter, rec));
+            }
+        }
+        spans
+    }
+}
+
+// Permet de partager un objet entre la scène et la liste des lumières
+impl<T: Hittable + ?Sized> Hittable for Arc<T> {
+    fn hit(&self, ray: Ray, ray_t: Interval, rec: &mut HitRecord) -> bool {
+        (**self).hit(ray, ray_t, rec)
+    }
+
+    fn bounding_box(&self) -> Aabb {
+        (**self).bounding_box()
+    }
+
+    fn occluded(&self, ray: Ray, ray_t: Interval) -> bool {
+        (**self).occluded(ray, ray_t)
+    }
+
+    fn intersections(&self, ray: Ray, ray_t: Interval, hits: &mut Vec<HitRecord>) {
+        (**self).intersections(ray, ray_t, hits)
+    }
 }
 
 #[derive(Clone)]
@@ -15,7 +75,17 @@ pub struct HitRecord {
     pub p: Point3,
     pub normal: Vec3,
     pub front_face: bool,
+    pub u: f32, // coordonnées de texture du point touché
+    pub v: f32,
     pub material: Option<Arc<dyn Material>>,
+    // Milieu délimité par la surface touchée : on y entre par la face avant
+    pub medium: Option<Arc<dyn Medium>>,
+}
+
+impl Default for HitRecord {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl HitRecord {
@@ -25,7 +95,10 @@ impl HitRecord {
             p: Point3::new(0.0, 0.0, 0.0),
             normal: Vec3::new(0.0, 0.0, 0.0),
             front_face: false,
+            u: 0.0,
+            v: 0.0,
             material: None,
+            medium: None,
         }
     }
 
@@ -33,4 +106,18 @@ impl HitRecord {
         self.front_face = dot(ray.direction(), outward_normal) < 0.0;
         self.normal = if self.front_face { outward_normal } else { -outward_normal };
     }
-}
\ No newline at end of file
+}
+
+// Portion du rayon comprise dans un solide, de `enter` à `exit`
+#[derive(Clone)]
+pub struct Span {
+    pub t: Interval,
+    pub enter: HitRecord,
+    pub exit: HitRecord,
+}
+
+impl Span {
+    pub fn new(enter: HitRecord, exit: HitRecord) -> Self {
+        Span { t: Interval::new(enter.t.min, exit.t.min), enter, exit }
+    }
+}
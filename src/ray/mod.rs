@@ -1,4 +1,6 @@
 pub mod vec3;
 pub mod color;
+#[allow(clippy::module_inception)]
 pub mod ray;
 pub mod hittable;
+pub mod transform;
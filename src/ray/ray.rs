@@ -4,13 +4,19 @@ use crate::ray::vec3::{Vec3, Point3};
 pub struct Ray {
     orig: Point3,
     dir: Vec3,
+    time: f32, // instant de l'obturation auquel le rayon est lancé
 }
 
 impl Ray {
     pub fn new(origin: Point3, direction: Vec3) -> Self {
+        Ray::with_time(origin, direction, 0.0)
+    }
+
+    pub fn with_time(origin: Point3, direction: Vec3, time: f32) -> Self {
         Ray {
             orig: origin,
             dir: direction,
+            time,
         }
     }
 
@@ -22,6 +28,10 @@ impl Ray {
         self.dir
     }
 
+    pub fn time(&self) -> f32 {
+        self.time
+    }
+
     pub fn at(&self, t: f32) -> Point3 {
         self.orig + self.dir * t
     }
@@ -0,0 +1,349 @@
+use crate::ray::vec3::{Vec3, Point3};
+use crate::ray::vec3::dot;
+use crate::object::light::orthonormal_basis;
+use std::ops;
+
+// Matrice 4x4 en lignes, appliquée aux vecteurs colonnes (x, y, z, w)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Matrix4 {
+    pub m: [[f32; 4]; 4],
+}
+
+impl Matrix4 {
+    pub const IDENTITY: Matrix4 = Matrix4 {
+        m: [
+            [1.0, 0.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ],
+    };
+
+    pub fn new(m: [[f32; 4]; 4]) -> Self {
+        Matrix4 { m }
+    }
+
+    pub fn translation(offset: Vec3) -> Self {
+        let mut result = Matrix4::IDENTITY;
+        result.m[0][3] = offset.x();
+        result.m[1][3] = offset.y();
+        result.m[2][3] = offset.z();
+        result
+    }
+
+    pub fn scaling(factors: Vec3) -> Self {
+        let mut result = Matrix4::IDENTITY;
+        result.m[0][0] = factors.x();
+        result.m[1][1] = factors.y();
+        result.m[2][2] = factors.z();
+        result
+    }
+
+    // Rotations en radians, dans le sens direct autour de chaque axe
+    pub fn rotation_x(theta: f32) -> Self {
+        let (sin, cos) = theta.sin_cos();
+        let mut result = Matrix4::IDENTITY;
+        result.m[1][1] = cos;
+        result.m[1][2] = -sin;
+        result.m[2][1] = sin;
+        result.m[2][2] = cos;
+        result
+    }
+
+    pub fn rotation_y(theta: f32) -> Self {
+        let (sin, cos) = theta.sin_cos();
+        let mut result = Matrix4::IDENTITY;
+        result.m[0][0] = cos;
+        result.m[0][2] = sin;
+        result.m[2][0] = -sin;
+        result.m[2][2] = cos;
+        result
+    }
+
+    pub fn rotation_z(theta: f32) -> Self {
+        let (sin, cos) = theta.sin_cos();
+        let mut result = Matrix4::IDENTITY;
+        result.m[0][0] = cos;
+        result.m[0][1] = -sin;
+        result.m[1][0] = sin;
+        result.m[1][1] = cos;
+        result
+    }
+
+    // Rotation d'angle `theta` autour d'un axe quelconque (formule de Rodrigues)
+    pub fn rotation(axis: Vec3, theta: f32) -> Self {
+        let a = axis.unit_vector();
+        let (x, y, z) = (a.x(), a.y(), a.z());
+        let (sin, cos) = theta.sin_cos();
+        let t = 1.0 - cos;
+        Matrix4::new([
+            [t * x * x + cos, t * x * y - sin * z, t * x * z + sin * y, 0.0],
+            [t * x * y + sin * z, t * y * y + cos, t * y * z - sin * x, 0.0],
+            [t * x * z - sin * y, t * y * z + sin * x, t * z * z + cos, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ])
+    }
+
+    pub fn transpose(&self) -> Self {
+        let mut result = Matrix4::IDENTITY;
+        for (i, row) in result.m.iter_mut().enumerate() {
+            for (j, value) in row.iter_mut().enumerate() {
+                *value = self.m[j][i];
+            }
+        }
+        result
+    }
+
+    // Inverse par élimination de Gauss-Jordan avec pivot partiel, `None` si singulière
+    pub fn inverse(&self) -> Option<Self> {
+        let mut a = self.m;
+        let mut inv = Matrix4::IDENTITY.m;
+        for col in 0..4 {
+            let pivot = (col..4).max_by(|&i, &j| a[i][col].abs().total_cmp(&a[j][col].abs()))?;
+            if a[pivot][col].abs() < 1e-12 {
+                return None;
+            }
+            a.swap(col, pivot);
+            inv.swap(col, pivot);
+
+            let scale = 1.0 / a[col][col];
+            for k in 0..4 {
+                a[col][k] *= scale;
+                inv[col][k] *= scale;
+            }
+            for row in 0..4 {
+                if row != col {
+                    let factor = a[row][col];
+                    for k in 0..4 {
+                        a[row][k] -= factor * a[col][k];
+                        inv[row][k] -= factor * inv[col][k];
+                    }
+                }
+            }
+        }
+        Some(Matrix4::new(inv))
+    }
+
+    pub fn transform_point(&self, p: Point3) -> Point3 {
+        let m = &self.m;
+        let x = m[0][0] * p.x() + m[0][1] * p.y() + m[0][2] * p.z() + m[0][3];
+        let y = m[1][0] * p.x() + m[1][1] * p.y() + m[1][2] * p.z() + m[1][3];
+        let z = m[2][0] * p.x() + m[2][1] * p.y() + m[2][2] * p.z() + m[2][3];
+        let w = m[3][0] * p.x() + m[3][1] * p.y() + m[3][2] * p.z() + m[3][3];
+        if w == 1.0 || w == 0.0 { Point3::new(x, y, z) } else { Point3::new(x / w, y / w, z / w) }
+    }
+
+    // Les directions ne sont pas affectées par la translation
+    pub fn transform_vector(&self, v: Vec3) -> Vec3 {
+        let m = &self.m;
+        Vec3::new(
+            m[0][0] * v.x() + m[0][1] * v.y() + m[0][2] * v.z(),
+            m[1][0] * v.x() + m[1][1] * v.y() + m[1][2] * v.z(),
+            m[2][0] * v.x() + m[2][1] * v.y() + m[2][2] * v.z(),
+        )
+    }
+}
+
+impl Default for Matrix4 {
+    fn default() -> Self {
+        Matrix4::IDENTITY
+    }
+}
+
+impl ops::Mul for Matrix4 {
+    type Output = Matrix4;
+
+    fn mul(self, other: Matrix4) -> Matrix4 {
+        let mut result = [[0.0; 4]; 4];
+        for (i, row) in result.iter_mut().enumerate() {
+            for (j, value) in row.iter_mut().enumerate() {
+                *value = (0..4).map(|k| self.m[i][k] * other.m[k][j]).sum();
+            }
+        }
+        Matrix4::new(result)
+    }
+}
+
+// Transformation affine avec son inverse, calculée une seule fois
+#[derive(Clone, Copy, Debug)]
+pub struct Transform {
+    pub matrix: Matrix4,
+    pub inverse: Matrix4,
+}
+
+impl Transform {
+    pub const IDENTITY: Transform = Transform { matrix: Matrix4::IDENTITY, inverse: Matrix4::IDENTITY };
+
+    // `None` si la matrice n'est pas inversible (échelle nulle...)
+    pub fn new(matrix: Matrix4) -> Option<Self> {
+        Some(Transform { matrix, inverse: matrix.inverse()? })
+    }
+
+    pub fn translate(offset: Vec3) -> Self {
+        Transform { matrix: Matrix4::translation(offset), inverse: Matrix4::translation(-offset) }
+    }
+
+    pub fn scale(factors: Vec3) -> Option<Self> {
+        Transform::new(Matrix4::scaling(factors))
+    }
+
+    pub fn rotate_x(theta: f32) -> Self {
+        Transform { matrix: Matrix4::rotation_x(theta), inverse: Matrix4::rotation_x(-theta) }
+    }
+
+    pub fn rotate_y(theta: f32) -> Self {
+        Transform { matrix: Matrix4::rotation_y(theta), inverse: Matrix4::rotation_y(-theta) }
+    }
+
+    pub fn rotate_z(theta: f32) -> Self {
+        Transform { matrix: Matrix4::rotation_z(theta), inverse: Matrix4::rotation_z(-theta) }
+    }
+
+    pub fn rotate(axis: Vec3, theta: f32) -> Self {
+        Transform { matrix: Matrix4::rotation(axis, theta), inverse: Matrix4::rotation(axis, -theta) }
+    }
+
+    // Composition : `self` d'abord, puis `next`
+    pub fn then(&self, next: &Transform) -> Transform {
+        Transform { matrix: next.matrix * self.matrix, inverse: self.inverse * next.inverse }
+    }
+
+    pub fn point(&self, p: Point3) -> Point3 {
+        self.matrix.transform_point(p)
+    }
+
+    pub fn vector(&self, v: Vec3) -> Vec3 {
+        self.matrix.transform_vector(v)
+    }
+
+    // Les normales se transforment par la transposée de l'inverse
+    pub fn normal(&self, n: Vec3) -> Vec3 {
+        let m = &self.inverse.m;
+        Vec3::new(
+            m[0][0] * n.x() + m[1][0] * n.y() + m[2][0] * n.z(),
+            m[0][1] * n.x() + m[1][1] * n.y() + m[2][1] * n.z(),
+            m[0][2] * n.x() + m[1][2] * n.y() + m[2][2] * n.z(),
+        )
+    }
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Transform::IDENTITY
+    }
+}
+
+// Repère orthonormé d'origine `origin` dont l'axe y local suit `axis`, pour les
+// primitives définies autour d'un axe (cylindre, cône, tore)
+#[derive(Clone, Copy, Debug)]
+pub struct Frame {
+    pub origin: Point3,
+    pub x: Vec3,
+    pub y: Vec3,
+    pub z: Vec3,
+}
+
+impl Frame {
+    pub fn new(origin: Point3, axis: Vec3) -> Self {
+        let y = axis.unit_vector();
+        let (x, z) = orthonormal_basis(y);
+        Frame { origin, x, y, z }
+    }
+
+    pub fn to_local(&self, v: Vec3) -> Vec3 {
+        Vec3::new(dot(v, self.x), dot(v, self.y), dot(v, self.z))
+    }
+
+    pub fn point_to_local(&self, p: Point3) -> Point3 {
+        self.to_local(p - self.origin)
+    }
+
+    pub fn to_world(&self, v: Vec3) -> Vec3 {
+        self.x * v.x() + self.y * v.y() + self.z * v.z()
+    }
+
+    // Demi-étendue, le long de chaque axe du monde, d'un disque de rayon
+    // `radius` perpendiculaire à l'axe du repère
+    pub fn disk_extent(&self, radius: f32) -> Vec3 {
+        let a = self.y;
+        Vec3::new(
+            radius * (1.0 - a.x() * a.x()).max(0.0).sqrt(),
+            radius * (1.0 - a.y() * a.y()).max(0.0).sqrt(),
+            radius * (1.0 - a.z() * a.z()).max(0.0).sqrt(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_near(a: &Matrix4, b: &Matrix4) {
+        for i in 0..4 {
+            for j in 0..4 {
+                assert!((a.m[i][j] - b.m[i][j]).abs() < 1e-5, "{a:?} != {b:?}");
+            }
+        }
+    }
+
+    fn assert_inverse(m: Matrix4) {
+        let inv = m.inverse().expect("invertible");
+        assert_near(&(inv * m), &Matrix4::IDENTITY);
+        assert_near(&(m * inv), &Matrix4::IDENTITY);
+    }
+
+    #[test]
+    fn inverse_of_affine_matrices() {
+        assert_inverse(Matrix4::IDENTITY);
+        assert_inverse(Matrix4::translation(Vec3::new(1.0, -2.0, 3.0)));
+        assert_inverse(Matrix4::scaling(Vec3::new(2.0, 0.5, -4.0)));
+        assert_inverse(Matrix4::rotation(Vec3::new(1.0, 2.0, 3.0), 0.7));
+        assert_inverse(Matrix4::new([
+            [2.0, 1.0, 0.0, 3.0],
+            [0.5, 3.0, -1.0, 0.0],
+            [1.0, 0.0, 4.0, -2.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ]));
+    }
+
+    #[test]
+    fn inverse_needs_pivoting() {
+        // Zéro sur la diagonale : l'élimination doit échanger les lignes
+        assert_inverse(Matrix4::new([
+            [0.0, 1.0, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [1.0, 0.0, 0.0, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ]));
+    }
+
+    #[test]
+    fn singular_matrix_has_no_inverse() {
+        let singular = Matrix4::new([
+            [1.0, 2.0, 3.0, 0.0],
+            [2.0, 4.0, 6.0, 0.0],
+            [0.0, 1.0, 1.0, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ]);
+        assert!(singular.inverse().is_none());
+        assert!(Transform::new(singular).is_none());
+        assert!(Transform::scale(Vec3::new(1.0, 0.0, 1.0)).is_none());
+    }
+
+    #[test]
+    fn then_applies_self_first() {
+        let t = Transform::translate(Vec3::new(1.0, 0.0, 0.0)).then(&Transform::rotate_z(std::f32::consts::FRAC_PI_2));
+        let p = t.point(Point3::new(0.0, 0.0, 0.0));
+        assert!((p - Point3::new(0.0, 1.0, 0.0)).length() < 1e-5);
+        assert_near(&(t.inverse * t.matrix), &Matrix4::IDENTITY);
+    }
+
+    #[test]
+    fn normals_stay_perpendicular() {
+        let t = Transform::scale(Vec3::new(2.0, 1.0, 1.0)).unwrap();
+        // Plan x = y : tangente (1, 1, 0), normale (1, -1, 0)
+        let tangent = t.vector(Vec3::new(1.0, 1.0, 0.0));
+        let normal = t.normal(Vec3::new(1.0, -1.0, 0.0));
+        assert!(tangent.dot(&normal).abs() < 1e-5);
+    }
+}
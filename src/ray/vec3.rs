@@ -177,11 +177,16 @@ impl Vec3 {
         *self - 2.0 * dot(*self, *n) * *n
     }
 
-    pub fn refract(&self, n: &Vec3, etai_over_etat: f32) -> Vec3 {
+    // Direction réfractée (vecteurs unitaires), ou None en cas de réflexion totale interne
+    pub fn refract(&self, n: &Vec3, etai_over_etat: f32) -> Option<Vec3> {
         let cos_theta = f32::min(dot(-*self, *n), 1.0);
         let r_out_perp = etai_over_etat * (*self + cos_theta * *n);
-        let r_out_parallel = -((1.0 - r_out_perp.length_squared()).abs().sqrt()) * *n;
-        r_out_perp + r_out_parallel
+        let k = 1.0 - r_out_perp.length_squared();
+        if k < 0.0 {
+            return None;
+        }
+        let r_out_parallel = -k.sqrt() * *n;
+        Some(r_out_perp + r_out_parallel)
     }
 }
 
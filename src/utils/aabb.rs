@@ -0,0 +1,136 @@
+use crate::ray::ray::Ray;
+use crate::ray::vec3::{Vec3, Point3};
+use crate::utils::interval::Interval;
+
+// Boîte englobante alignée sur les axes
+#[derive(Clone, Copy, Debug)]
+pub struct Aabb {
+    pub x: Interval,
+    pub y: Interval,
+    pub z: Interval,
+}
+
+impl Aabb {
+    pub const EMPTY: Aabb = Aabb { x: Interval::EMPTY, y: Interval::EMPTY, z: Interval::EMPTY };
+    pub const UNIVERSE: Aabb = Aabb { x: Interval::UNIVERSE, y: Interval::UNIVERSE, z: Interval::UNIVERSE };
+
+    pub fn new(x: Interval, y: Interval, z: Interval) -> Self {
+        let mut bbox = Aabb { x, y, z };
+        bbox.pad_to_minimums();
+        bbox
+    }
+
+    // Boîte définie par deux coins opposés, dans n'importe quel ordre
+    pub fn from_points(a: Point3, b: Point3) -> Self {
+        Aabb::new(
+            Interval::new(a.x().min(b.x()), a.x().max(b.x())),
+            Interval::new(a.y().min(b.y()), a.y().max(b.y())),
+            Interval::new(a.z().min(b.z()), a.z().max(b.z())),
+        )
+    }
+
+    pub fn surrounding(a: &Aabb, b: &Aabb) -> Self {
+        Aabb {
+            x: Interval::enclosing(&a.x, &b.x),
+            y: Interval::enclosing(&a.y, &b.y),
+            z: Interval::enclosing(&a.z, &b.z),
+        }
+    }
+
+    pub fn overlap(a: &Aabb, b: &Aabb) -> Self {
+        Aabb {
+            x: Interval::overlap(&a.x, &b.x),
+            y: Interval::overlap(&a.y, &b.y),
+            z: Interval::overlap(&a.z, &b.z),
+        }
+    }
+
+    pub fn axis_interval(&self, axis: usize) -> Interval {
+        match axis {
+            1 => self.y,
+            2 => self.z,
+            _ => self.x,
+        }
+    }
+
+    pub fn longest_axis(&self) -> usize {
+        let (x, y, z) = (self.x.size(), self.y.size(), self.z.size());
+        if x > y {
+            if x > z { 0 } else { 2 }
+        } else if y > z {
+            1
+        } else {
+            2
+        }
+    }
+
+    pub fn centroid(&self) -> Point3 {
+        Point3::new(
+            0.5 * (self.x.min + self.x.max),
+            0.5 * (self.y.min + self.y.max),
+            0.5 * (self.z.min + self.z.max),
+        )
+    }
+
+    pub fn surface_area(&self) -> f32 {
+        if self.x.is_empty() || self.y.is_empty() || self.z.is_empty() {
+            return 0.0;
+        }
+        let (dx, dy, dz) = (self.x.size(), self.y.size(), self.z.size());
+        2.0 * (dx * dy + dy * dz + dz * dx)
+    }
+
+    // Faux pour les objets infinis, comme les plans
+    pub fn is_bounded(&self) -> bool {
+        [self.x, self.y, self.z].iter().all(|i| i.min.is_finite() && i.max.is_finite())
+    }
+
+    // Vrai si un des axes est vide, par exemple après `overlap` de boîtes disjointes
+    pub fn is_empty(&self) -> bool {
+        [self.x, self.y, self.z].iter().any(|i| i.is_empty())
+    }
+
+    // Boîte déplacée de `offset`
+    pub fn offset(&self, offset: Vec3) -> Aabb {
+        let shift = |i: Interval, d: f32| Interval::new(i.min + d, i.max + d);
+        Aabb { x: shift(self.x, offset.x()), y: shift(self.y, offset.y()), z: shift(self.z, offset.z()) }
+    }
+
+    pub fn hit(&self, ray: &Ray, ray_t: Interval) -> bool {
+        self.clip(ray, ray_t).is_some()
+    }
+
+    // Partie de `ray_t` où le rayon traverse la boîte
+    pub fn clip(&self, ray: &Ray, ray_t: Interval) -> Option<Interval> {
+        let origin = ray.origin();
+        let direction = ray.direction();
+        let mut t_min = ray_t.min;
+        let mut t_max = ray_t.max;
+
+        for axis in 0..3 {
+            let ax = self.axis_interval(axis);
+            let adinv = 1.0 / direction[axis];
+            let t0 = (ax.min - origin[axis]) * adinv;
+            let t1 = (ax.max - origin[axis]) * adinv;
+            let (near, far) = if t0 < t1 { (t0, t1) } else { (t1, t0) };
+            if near > t_min {
+                t_min = near;
+            }
+            if far < t_max {
+                t_max = far;
+            }
+            if t_max <= t_min {
+                return None;
+            }
+        }
+        Some(Interval::new(t_min, t_max))
+    }
+
+    // Évite les boîtes plates (triangles alignés sur un axe)
+    fn pad_to_minimums(&mut self) {
+        let delta = 0.0001;
+        if self.x.size() < delta { self.x = self.x.expand(delta); }
+        if self.y.size() < delta { self.y = self.y.expand(delta); }
+        if self.z.size() < delta { self.z = self.z.expand(delta); }
+    }
+}
@@ -0,0 +1,93 @@
+use crate::utils::image::Image;
+use crate::utils::utils::random_double;
+use std::f32::consts::PI;
+use std::io;
+use std::path::Path;
+use std::sync::Arc;
+
+// Forme du diaphragme, qui donne sa forme au bokeh des zones floues
+#[derive(Clone)]
+pub enum Aperture {
+    Circle,
+    // Polygone régulier inscrit dans le cercle unité, rotation en radians
+    Polygon { blades: u32, rotation: f32 },
+    Mask(Arc<ApertureMask>),
+}
+
+impl Aperture {
+    pub fn polygon(blades: u32, rotation: f32) -> Self {
+        assert!(blades >= 3, "an aperture polygon needs at least 3 blades");
+        Aperture::Polygon { blades, rotation }
+    }
+
+    // Point uniforme de l'ouverture, dans le disque unité
+    pub fn sample(&self) -> (f32, f32) {
+        match self {
+            Aperture::Circle => loop {
+                let x = 2.0 * random_double() - 1.0;
+                let y = 2.0 * random_double() - 1.0;
+                if x * x + y * y < 1.0 {
+                    return (x, y);
+                }
+            },
+            Aperture::Polygon { blades, rotation } => {
+                // Les triangles centre-arête ont tous la même aire
+                let k = ((random_double() * *blades as f32) as u32).min(blades - 1);
+                let step = 2.0 * PI / *blades as f32;
+                let a0 = rotation + k as f32 * step;
+                let a1 = a0 + step;
+                let s = random_double().sqrt();
+                let t = random_double();
+                let (b0, b1) = (s * (1.0 - t), s * t);
+                (b0 * a0.cos() + b1 * a1.cos(), b0 * a0.sin() + b1 * a1.sin())
+            }
+            Aperture::Mask(mask) => mask.sample(),
+        }
+    }
+}
+
+// Image en niveaux de gris : la transmission de chaque pixel pondère son tirage
+pub struct ApertureMask {
+    width: usize,
+    height: usize,
+    cdf: Vec<f32>,
+}
+
+impl ApertureMask {
+    // `None` si l'image ne laisse passer aucune lumière
+    pub fn new(image: &Image) -> Option<Self> {
+        let mut cdf = Vec::with_capacity(image.pixels().len());
+        let mut total = 0.0;
+        for pixel in image.pixels() {
+            let luminance = 0.2126 * pixel.x() + 0.7152 * pixel.y() + 0.0722 * pixel.z();
+            total += luminance.max(0.0);
+            cdf.push(total);
+        }
+        if total <= 0.0 {
+            return None;
+        }
+        for value in &mut cdf {
+            *value /= total;
+        }
+        Some(ApertureMask { width: image.width(), height: image.height(), cdf })
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let image = Image::read_ppm(path)?;
+        ApertureMask::new(&image)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "aperture mask is completely black"))
+    }
+
+    // Le plus grand côté de l'image couvre le diamètre de l'objectif
+    pub fn sample(&self) -> (f32, f32) {
+        let r = random_double();
+        let index = self.cdf.partition_point(|&c| c < r).min(self.cdf.len() - 1);
+        let x = (index % self.width) as f32 + random_double();
+        let y = (index / self.width) as f32 + random_double();
+        let size = self.width.max(self.height) as f32;
+        let u = (2.0 * x - self.width as f32) / size;
+        // Lignes de l'image de haut en bas, axe v de l'objectif vers le haut
+        let v = (self.height as f32 - 2.0 * y) / size;
+        (u, v)
+    }
+}
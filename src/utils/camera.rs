@@ -1,10 +1,19 @@
 use crate::ray::ray::Ray;
 use crate::ray::vec3::{Vec3, Point3};
+use crate::ray::vec3::dot;
 use crate::ray::hittable::{Hittable, HitRecord};
 use crate::utils::interval::Interval;
-use crate::ray::color::write_color;
-use crate::utils::utils::random_double;
-use minifb::{Window, WindowOptions, Key};
+use crate::utils::image::Image;
+use crate::utils::aperture::Aperture;
+use crate::utils::utils::{random_double, seed_rng};
+use crate::object::light::{Light, LightList};
+use crate::object::material::Material;
+use crate::object::medium::{Atmosphere, scatter_record};
+use crate::object::volume::Medium;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::sync::Arc;
+use std::thread;
 
 pub struct Camera {
     pub aspect_ratio : f32,
@@ -21,22 +30,84 @@ pub struct Camera {
     pub look_from: Point3,
     pub look_at: Point3,
     pub vup: Vec3,
-    buffer: Vec<u32>,
-    window: Option<Window>,
+    pub threads: usize, // 0 = autant que de coeurs disponibles
+    pub tile_size: i32,
+    pub background: Background,
+    pub lights: LightList, // émetteurs échantillonnés directement à chaque rebond
+    pub seed: Option<u64>, // graine fixe : image identique quel que soit le nombre de threads
+    pub defocus_angle: f32, // ouverture vue depuis le plan net, en degrés (0 = sténopé)
+    pub focus_dist: f32, // distance du plan net (0 = distance jusqu'à `look_at`)
+    pub aperture: Aperture,
+    pub defocus_disk_u: Vec3,
+    pub defocus_disk_v: Vec3,
+    pub shutter_open: f32, // les objets en mouvement se déplacent entre les temps 0 et 1
+    pub shutter_close: f32,
+    pub atmosphere: Option<Atmosphere>, // brume homogène entre les surfaces
+}
+
+// Lumière renvoyée par les rayons qui ne touchent aucun objet
+#[derive(Clone, Copy, Debug)]
+pub enum Background {
+    // Dégradé vertical de l'horizon (bas) vers le zénith (haut)
+    Gradient { horizon: Vec3, zenith: Vec3 },
+    Solid(Vec3),
+}
+
+impl Background {
+    pub fn sky() -> Self {
+        Background::Gradient { horizon: Vec3::new(1.0, 1.0, 1.0), zenith: Vec3::new(0.5, 0.7, 1.0) }
+    }
+
+    pub fn black() -> Self {
+        Background::Solid(Vec3::new(0.0, 0.0, 0.0))
+    }
+
+    pub fn color(&self, ray: &Ray) -> Vec3 {
+        match *self {
+            Background::Gradient { horizon, zenith } => {
+                let unit_direction = ray.direction().unit_vector();
+                let a = 0.5 * (unit_direction.y() + 1.0);
+                horizon * (1.0 - a) + zenith * a
+            }
+            Background::Solid(color) => color,
+        }
+    }
+}
+
+// Région rectangulaire de l'image rendue par un seul thread (bornes max exclues)
+#[derive(Clone, Copy, Debug)]
+pub struct Tile {
+    pub x0: usize,
+    pub y0: usize,
+    pub x1: usize,
+    pub y1: usize,
+}
+
+// Reçoit les tuiles terminées sur le thread principal pendant le rendu
+pub trait RenderObserver {
+    fn tile_done(&mut self, image: &Image, tile: Tile);
+}
+
+// Milieu dans lequel part un rayon de direction `direction` depuis `rec` :
+// celui de la surface s'il va vers l'intérieur, sinon le milieu courant
+fn medium_towards(rec: &HitRecord, current: &Option<Arc<dyn Medium>>, direction: Vec3) -> Option<Arc<dyn Medium>> {
+    let Some(boundary) = &rec.medium else {
+        return current.clone();
+    };
+    let outward = if rec.front_face { rec.normal } else { -rec.normal };
+    if dot(direction, outward) < 0.0 { Some(boundary.clone()) } else { None }
+}
+
+// Heuristique de puissance (beta = 2) de Veach pour combiner deux stratégies
+fn power_heuristic(pdf: f32, other_pdf: f32) -> f32 {
+    let a = pdf * pdf;
+    let b = other_pdf * other_pdf;
+    if a + b > 0.0 { a / (a + b) } else { 0.0 }
 }
 
 impl Camera {
     pub fn new(aspect_ratio: f32, image_width: i32, samples_per_pixel: i32 , look_from: Point3, look_at: Point3, vup: Vec3) -> Self {
         let image_height = (image_width as f32 / aspect_ratio) as i32;
-        let buffer_size = (image_width * image_height) as usize;
-        let mut window = Window::new(
-            "Ray Tracer Preview",
-            image_width as usize,
-            image_height as usize,
-            WindowOptions::default(),
-        ).unwrap();
-        
-        window.limit_update_rate(Some(std::time::Duration::from_micros(16600)));
 
         Camera {
             max_depth: 20,
@@ -53,8 +124,19 @@ impl Camera {
             pixel_delta_u: Vec3::new(0.0, 0.0, 0.0),
             pixel_delta_v: Vec3::new(0.0, 0.0, 0.0),
             pixel00_loc: Point3::new(0.0, 0.0, 0.0),
-            buffer: vec![0; buffer_size],
-            window: Some(window),
+            threads: 0,
+            tile_size: 32,
+            background: Background::sky(),
+            lights: LightList::new(),
+            seed: None,
+            defocus_angle: 0.0,
+            focus_dist: 0.0,
+            aperture: Aperture::Circle,
+            defocus_disk_u: Vec3::new(0.0, 0.0, 0.0),
+            defocus_disk_v: Vec3::new(0.0, 0.0, 0.0),
+            shutter_open: 0.0,
+            shutter_close: 1.0,
+            atmosphere: None,
         }
     }
 
@@ -65,10 +147,11 @@ impl Camera {
         self.pixel_sample_scale = 1.0 / self.samples_per_pixel as f32;
         // Viewport dimensions
         self.center = self.look_from;
-        let focal_length = (self.look_from - self.look_at).length();
+        // Le viewport est placé sur le plan net
+        let focus_dist = if self.focus_dist > 0.0 { self.focus_dist } else { (self.look_from - self.look_at).length() };
         let theta = self.vfov.to_radians();
         let h = (theta / 2.0).tan();
-        let viewport_height = 2.0 * h * focal_length;
+        let viewport_height = 2.0 * h * focus_dist;
         let viewport_width = viewport_height * self.aspect_ratio;
         //Calculte the u , v , w vectors 
         let w = (self.look_from - self.look_at).unit_vector();
@@ -81,96 +164,253 @@ impl Camera {
         self.pixel_delta_u = viewport_u / self.image_width as f32;
         self.pixel_delta_v = viewport_v / self.image_height as f32;
         // Calculate the location of the upper left pixel
-        let viewport_upper_left = self.center - (focal_length * w) - (viewport_u / 2.0) - (viewport_v / 2.0);
+        let viewport_upper_left = self.center - (focus_dist * w) - (viewport_u / 2.0) - (viewport_v / 2.0);
         self.pixel00_loc = viewport_upper_left + (self.pixel_delta_u + self.pixel_delta_v) * 0.5;
+        // Rayons du disque de l'objectif, dans lequel s'inscrit l'ouverture
+        let defocus_radius = focus_dist * (self.defocus_angle / 2.0).to_radians().tan();
+        self.defocus_disk_u = u * defocus_radius;
+        self.defocus_disk_v = v * defocus_radius;
+    }
+
+    pub fn ray_color(&self, ray: Ray, world: &dyn Hittable, depth: i32) -> Vec3 {
+        self.trace(ray, world, depth, 0.0, None)
     }
 
-    pub fn ray_color(ray: Ray, world: &dyn Hittable, depth: i32) -> Vec3 {
+    // `bsdf_pdf` est la densité avec laquelle le rebond précédent a choisi `ray`,
+    // ou 0 s'il n'a pas pu être échantillonné par les lumières (caméra, spéculaire) ;
+    // `medium` est le milieu dans lequel part le rayon
+    fn trace(&self, ray: Ray, world: &dyn Hittable, depth: i32, bsdf_pdf: f32,
+             medium: Option<Arc<dyn Medium>>) -> Vec3 {
         if depth <= 0 {
             return Vec3::new(0.0, 0.0, 0.0);
         }
+        // Les contours sans matériau des milieux sont traversés sans compter de rebond
+        let mut medium = medium;
+        let mut t_min = 0.001;
+        let mut volume_emission = Vec3::new(0.0, 0.0, 0.0);
         let mut rec = HitRecord::new();
-        if world.hit(ray, Interval::new(0.001, f32::INFINITY), &mut rec) {
-            let mut scattered = Ray::new(Point3::new(0.0, 0.0, 0.0), Vec3::new(0.0, 0.0, 0.0));
-            let mut attenuation = Vec3::new(0.0, 0.0, 0.0);
-            if let Some(ref material) = rec.material {
-                if material.scatter(&ray, &rec, &mut attenuation, &mut scattered) {
-                    return Camera::ray_color(scattered, world, depth - 1) * attenuation;
+        loop {
+            let hit = world.hit(ray, Interval::new(t_min, f32::INFINITY), &mut rec);
+            let t_max = if hit { rec.t.min } else { f32::INFINITY };
+            if let Some(event) = medium.as_ref().and_then(|m| m.sample(ray, Interval::new(t_min, t_max))) {
+                volume_emission = event.emission;
+                scatter_record(ray, event.t, &event.phase, &mut rec);
+                break;
+            }
+            if !hit {
+                return self.background.color(&ray);
+            }
+            match (&rec.material, &rec.medium) {
+                (None, Some(boundary)) => {
+                    medium = if rec.front_face { Some(boundary.clone()) } else { None };
+                    t_min = rec.t.min + 0.001;
+                    rec = HitRecord::new();
                 }
+                _ => break,
             }
+        }
+        if let Some(atmosphere) = &self.atmosphere {
+            if atmosphere.scatter(ray, &mut rec) {
+                volume_emission = Vec3::new(0.0, 0.0, 0.0);
+            }
+        }
+
+        let Some(material) = rec.material.clone() else {
+            return volume_emission;
+        };
+        // L'émission des volumes n'est pas échantillonnée par les lumières : pas de MIS
+        let mut emitted = material.emitted(&ray, &rec);
+        if bsdf_pdf > 0.0 && !self.lights.is_empty() {
+            let light_pdf = self.lights.pdf(ray.origin(), ray.direction(), ray.time());
+            emitted *= power_heuristic(bsdf_pdf, light_pdf);
+        }
+        emitted += volume_emission;
+
+        let mut scattered = Ray::new(Point3::new(0.0, 0.0, 0.0), Vec3::new(0.0, 0.0, 0.0));
+        let mut attenuation = Vec3::new(0.0, 0.0, 0.0);
+        if !material.scatter(&ray, &rec, &mut attenuation, &mut scattered) {
+            return emitted;
+        }
+
+        let scattering_pdf = material.scattering_pdf(&ray, &rec, &scattered);
+        let direct = if scattering_pdf > 0.0 {
+            self.sample_lights(world, &ray, &rec, material.as_ref(), &medium)
+        } else {
+            Vec3::new(0.0, 0.0, 0.0)
+        };
+        let next_medium = medium_towards(&rec, &medium, scattered.direction());
+        emitted + direct + self.trace(scattered, world, depth - 1, scattering_pdf, next_medium) * attenuation
+    }
+
+    // Estimation directe : un rayon d'ombre vers une lumière, pondéré par MIS
+    fn sample_lights(&self, world: &dyn Hittable, ray: &Ray, rec: &HitRecord, material: &dyn Material,
+                     medium: &Option<Arc<dyn Medium>>) -> Vec3 {
+        let Some(sample) = self.lights.sample_li(rec.p, ray.time()) else {
+            return Vec3::new(0.0, 0.0, 0.0);
+        };
+        if sample.pdf <= 0.0 {
+            return Vec3::new(0.0, 0.0, 0.0);
+        }
+
+        let shadow_ray = Ray::with_time(rec.p, sample.wi, ray.time());
+        let f = material.eval(ray, rec, &shadow_ray);
+        if f.near_zero() {
             return Vec3::new(0.0, 0.0, 0.0);
         }
+        let medium = medium_towards(rec, medium, sample.wi);
+        let mut transmittance = self.transmittance(world, shadow_ray, sample.distance - 0.001, medium);
+        if transmittance <= 0.0 {
+            return Vec3::new(0.0, 0.0, 0.0);
+        }
+        if let Some(atmosphere) = &self.atmosphere {
+            transmittance *= atmosphere.transmittance(sample.distance);
+        }
 
-        let unit_direction = ray.direction().unit_vector();
-        let a = 0.5 * (unit_direction.y() + 1.0);
-        Vec3::new(1.0, 1.0, 1.0) * (1.0 - a) + Vec3::new(0.5, 0.7, 1.0) * a
+        let scattering_pdf = material.scattering_pdf(ray, rec, &shadow_ray);
+        f * sample.radiance * (transmittance * power_heuristic(sample.pdf, scattering_pdf) / sample.pdf)
+    }
+
+    // Lumière transmise le long d'un rayon d'ombre jusqu'à `t_max` : nulle si
+    // une surface le bloque, atténuée par les milieux dont il traverse les contours
+    fn transmittance(&self, world: &dyn Hittable, ray: Ray, t_max: f32, medium: Option<Arc<dyn Medium>>) -> f32 {
+        let segment = Interval::new(0.001, t_max);
+        if !world.occluded(ray, segment) {
+            return medium.map_or(1.0, |m| m.transmittance(ray, segment));
+        }
+        let mut medium = medium;
+        let mut t_min = 0.001;
+        let mut transmittance = 1.0;
+        loop {
+            let mut rec = HitRecord::new();
+            let hit = world.hit(ray, Interval::new(t_min, t_max), &mut rec);
+            let t_end = if hit { rec.t.min } else { t_max };
+            if let Some(current) = &medium {
+                transmittance *= current.transmittance(ray, Interval::new(t_min, t_end));
+            }
+            if !hit {
+                return transmittance;
+            }
+            let (None, Some(boundary)) = (&rec.material, &rec.medium) else {
+                return 0.0;
+            };
+            medium = if rec.front_face { Some(boundary.clone()) } else { None };
+            t_min = rec.t.min + 0.001;
+        }
     }
 
+    // Rayon partant d'un point du disque de l'objectif vers un point aléatoire du pixel (i, j),
+    // à un instant aléatoire de l'obturation
     fn get_ray(&self, i: i32, j: i32) -> Ray {
         let offset = self.sample_square();
-        let pixel_sample = self.pixel00_loc + (i as f32 * self.pixel_delta_u + j as f32 * self.pixel_delta_v) + offset;
-        let ray_direction = pixel_sample - self.center;
-        Ray::new(self.center, ray_direction)
+        let pixel_sample = self.pixel00_loc
+            + ((i as f32 + offset.x()) * self.pixel_delta_u)
+            + ((j as f32 + offset.y()) * self.pixel_delta_v);
+        let ray_origin = if self.defocus_angle <= 0.0 { self.center } else { self.defocus_disk_sample() };
+        let ray_direction = pixel_sample - ray_origin;
+        let ray_time = self.shutter_open + random_double() * (self.shutter_close - self.shutter_open);
+        Ray::with_time(ray_origin, ray_direction, ray_time)
     }
 
+    // Décalage dans le carré unité [-0.5, 0.5]², en pixels
     fn sample_square(&self) -> Vec3 {
-        let px = -0.5 + random_double();
-        let py = -0.5 + random_double();
-        Vec3::new(px, py, 0.0) * self.pixel_sample_scale
-    }
-
-    fn update_pixel(&mut self, x: i32, y: i32, color: Vec3) {
-        let idx = (y * self.image_width + x) as usize;
-        let r = (color.x() * 255.0) as u32;
-        let g = (color.y() * 255.0) as u32;
-        let b = (color.z() * 255.0) as u32;
-        self.buffer[idx] = (r << 16) | (g << 8) | b;
-        
-        // Mettre à jour la fenêtre tous les N pixels
-        if idx % 1000 == 0 {
-            if let Some(window) = &mut self.window {
-                window.update_with_buffer(&self.buffer, 
-                    self.image_width as usize, 
-                    self.image_height as usize
-                ).unwrap();
-            }
+        Vec3::new(random_double() - 0.5, random_double() - 0.5, 0.0)
+    }
+
+    fn defocus_disk_sample(&self) -> Point3 {
+        let (x, y) = self.aperture.sample();
+        self.center + (x * self.defocus_disk_u) + (y * self.defocus_disk_v)
+    }
+
+    fn thread_count(&self) -> usize {
+        if self.threads > 0 {
+            self.threads
+        } else {
+            thread::available_parallelism().map_or(1, |n| n.get())
         }
     }
 
-    pub fn render(&mut self, world: &dyn Hittable) {
-        self.intialize();
-        println!("P3");
-        println!("{} {}", self.image_width, self.image_height);
-        println!("255");
+    fn tiles(&self) -> Vec<Tile> {
+        let size = self.tile_size.max(1) as usize;
+        let width = self.image_width as usize;
+        let height = self.image_height as usize;
+        let mut tiles = Vec::new();
+        for y0 in (0..height).step_by(size) {
+            for x0 in (0..width).step_by(size) {
+                tiles.push(Tile {
+                    x0,
+                    y0,
+                    x1: (x0 + size).min(width),
+                    y1: (y0 + size).min(height),
+                });
+            }
+        }
+        tiles
+    }
 
-        for j in 0..self.image_height {
-            for i in 0..self.image_width {
+    // Couleur moyenne de chaque pixel de la tuile, ligne par ligne
+    fn render_tile(&self, world: &dyn Hittable, tile: Tile, tile_index: usize) -> Vec<Vec3> {
+        if let Some(seed) = self.seed {
+            seed_rng(seed ^ (tile_index as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15));
+        }
+        let mut pixels = Vec::with_capacity((tile.x1 - tile.x0) * (tile.y1 - tile.y0));
+        for j in tile.y0..tile.y1 {
+            for i in tile.x0..tile.x1 {
                 let mut pixel_color = Vec3::new(0.0, 0.0, 0.0);
                 for _ in 0..self.samples_per_pixel {
-                    let r: Ray = self.get_ray(i, j);
-                    pixel_color += Camera::ray_color(r, world, self.max_depth);
+                    let r: Ray = self.get_ray(i as i32, j as i32);
+                    pixel_color += self.ray_color(r, world, self.max_depth);
                 }
-                
-                // Gamma correction et normalisation
-                let scale = 1.0 / self.samples_per_pixel as f32;
-                let r = (pixel_color.x() * scale).sqrt();
-                let g = (pixel_color.y() * scale).sqrt();
-                let b = (pixel_color.z() * scale).sqrt();
-                
-                let final_color = Vec3::new(r, g, b);
-                self.update_pixel(i, j, final_color);
-                write_color(pixel_color, self.samples_per_pixel);
+                pixels.push(pixel_color * self.pixel_sample_scale);
             }
         }
+        pixels
+    }
 
-        // Afficher la fenêtre finale
-        if let Some(window) = &mut self.window {
-            while window.is_open() && !window.is_key_down(Key::Escape) {
-                window.update_with_buffer(&self.buffer, 
-                    self.image_width as usize, 
-                    self.image_height as usize
-                ).unwrap();
+    // Rendu sans fenêtre : l'image est simplement renvoyée
+    pub fn render(&mut self, world: &dyn Hittable) -> Image {
+        self.render_observed(world, None)
+    }
+
+    pub fn render_with(&mut self, world: &dyn Hittable, observer: &mut dyn RenderObserver) -> Image {
+        self.render_observed(world, Some(observer))
+    }
+
+    fn render_observed(&mut self, world: &dyn Hittable, mut observer: Option<&mut dyn RenderObserver>) -> Image {
+        self.intialize();
+        let mut image = Image::new(self.image_width as usize, self.image_height as usize);
+
+        let camera: &Camera = self;
+        let tiles = camera.tiles();
+        let next_tile = AtomicUsize::new(0);
+        let (sender, receiver) = mpsc::channel();
+
+        thread::scope(|scope| {
+            for _ in 0..camera.thread_count() {
+                let sender = sender.clone();
+                let tiles = &tiles;
+                let next_tile = &next_tile;
+                scope.spawn(move || loop {
+                    let index = next_tile.fetch_add(1, Ordering::Relaxed);
+                    let Some(&tile) = tiles.get(index) else { break };
+                    if sender.send((tile, camera.render_tile(world, tile, index))).is_err() {
+                        break;
+                    }
+                });
             }
-        }
+            drop(sender);
+
+            // Les tuiles terminées sont rassemblées par le thread principal
+            for (tile, pixels) in receiver {
+                let tile_width = tile.x1 - tile.x0;
+                for (k, pixel_color) in pixels.into_iter().enumerate() {
+                    image.set(tile.x0 + k % tile_width, tile.y0 + k / tile_width, pixel_color);
+                }
+                if let Some(observer) = observer.as_mut() {
+                    observer.tile_done(&image, tile);
+                }
+            }
+        });
+
+        image
     }
 }
\ No newline at end of file
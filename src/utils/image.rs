@@ -0,0 +1,136 @@
+use crate::ray::vec3::Vec3;
+use crate::ray::color::srgb_to_linear;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+// Image en mémoire : radiance linéaire moyenne de chaque pixel, ligne par ligne
+#[derive(Clone)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<Vec3>,
+}
+
+impl Image {
+    pub fn new(width: usize, height: usize) -> Self {
+        Image { width, height, pixels: vec![Vec3::new(0.0, 0.0, 0.0); width * height] }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn pixels(&self) -> &[Vec3] {
+        &self.pixels
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Vec3 {
+        self.pixels[y * self.width + x]
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, color: Vec3) {
+        self.pixels[y * self.width + x] = color;
+    }
+}
+
+impl Image {
+    // Lit un fichier PPM (P3 ou P6) et convertit ses couleurs en radiance linéaire
+    pub fn read_ppm(path: impl AsRef<Path>) -> io::Result<Image> {
+        let data = fs::read(path)?;
+        Image::parse_ppm(&data)
+    }
+
+    pub fn parse_ppm(data: &[u8]) -> io::Result<Image> {
+        let invalid = |message: &str| io::Error::new(io::ErrorKind::InvalidData, format!("invalid PPM: {message}"));
+
+        let mut pos = 0;
+        let mut header = Vec::new();
+        while header.len() < 4 {
+            let token = next_ppm_token(data, &mut pos).ok_or_else(|| invalid("truncated header"))?;
+            header.push(token);
+        }
+        let binary = match header[0].as_str() {
+            "P3" => false,
+            "P6" => true,
+            other => return Err(invalid(&format!("unsupported magic number {other}"))),
+        };
+        let parse = |token: &str| token.parse::<usize>().map_err(|_| invalid(&format!("bad number {token}")));
+        let width = parse(&header[1])?;
+        let height = parse(&header[2])?;
+        let max_value = parse(&header[3])?;
+        if max_value == 0 || max_value > 65535 {
+            return Err(invalid("max value must be between 1 and 65535"));
+        }
+
+        // Les dimensions viennent du fichier : elles sont comparées aux données
+        // restantes avant toute allocation
+        let count = width.checked_mul(height)
+            .and_then(|n| n.checked_mul(3))
+            .ok_or_else(|| invalid("image dimensions too large"))?;
+        let samples: Vec<usize> = if binary {
+            // Un seul blanc sépare l'en-tête des données binaires
+            pos += 1;
+            let bytes_per_sample = if max_value < 256 { 1 } else { 2 };
+            let body = count.checked_mul(bytes_per_sample)
+                .and_then(|size| data.get(pos..pos.checked_add(size)?))
+                .ok_or_else(|| invalid("truncated pixel data"))?;
+            if bytes_per_sample == 1 {
+                body.iter().map(|&b| b as usize).collect()
+            } else {
+                body.chunks_exact(2).map(|c| u16::from_be_bytes([c[0], c[1]]) as usize).collect()
+            }
+        } else {
+            // Chaque valeur texte occupe au moins un octet
+            if count > data.len().saturating_sub(pos) {
+                return Err(invalid("truncated pixel data"));
+            }
+            let mut samples = Vec::with_capacity(count);
+            while samples.len() < count {
+                let token = next_ppm_token(data, &mut pos).ok_or_else(|| invalid("truncated pixel data"))?;
+                samples.push(parse(&token)?);
+            }
+            samples
+        };
+
+        let scale = 1.0 / max_value as f32;
+        let mut image = Image::new(width, height);
+        for (pixel, rgb) in image.pixels.iter_mut().zip(samples.chunks_exact(3)) {
+            *pixel = Vec3::new(
+                srgb_to_linear(rgb[0] as f32 * scale),
+                srgb_to_linear(rgb[1] as f32 * scale),
+                srgb_to_linear(rgb[2] as f32 * scale),
+            );
+        }
+        Ok(image)
+    }
+}
+
+// Prochain mot de l'en-tête PPM, en sautant les blancs et les commentaires
+fn next_ppm_token(data: &[u8], pos: &mut usize) -> Option<String> {
+    loop {
+        while *pos < data.len() && data[*pos].is_ascii_whitespace() {
+            *pos += 1;
+        }
+        if *pos < data.len() && data[*pos] == b'#' {
+            while *pos < data.len() && data[*pos] != b'\n' {
+                *pos += 1;
+            }
+        } else {
+            break;
+        }
+    }
+    let start = *pos;
+    while *pos < data.len() && !data[*pos].is_ascii_whitespace() {
+        *pos += 1;
+    }
+    if start == *pos {
+        None
+    } else {
+        Some(String::from_utf8_lossy(&data[start..*pos]).into_owned())
+    }
+}
@@ -1,17 +1,36 @@
-#[derive(Clone)]
+#[derive(Clone, Copy, Debug)]
 pub struct Interval {
     pub min : f32,
     pub max : f32,
 }
 
 impl Interval {
+    pub const EMPTY : Interval = Interval { min: f32::INFINITY, max: f32::NEG_INFINITY };
+    pub const UNIVERSE : Interval = Interval { min: f32::NEG_INFINITY, max: f32::INFINITY };
+
     pub fn new(min: f32, max: f32) -> Self {
         Interval { min, max }
     }
 
+    // Plus petit intervalle contenant les deux intervalles
+    pub fn enclosing(a: &Interval, b: &Interval) -> Self {
+        Interval {
+            min: a.min.min(b.min),
+            max: a.max.max(b.max),
+        }
+    }
+
+    // Partie commune aux deux intervalles, vide s'ils sont disjoints
+    pub fn overlap(a: &Interval, b: &Interval) -> Self {
+        Interval {
+            min: a.min.max(b.min),
+            max: a.max.min(b.max),
+        }
+    }
+
     pub fn size(&self) -> f32{
         self.max - self.min
-    } 
+    }
 
     pub fn is_empty(&self) -> bool {
         self.min > self.max
@@ -21,6 +40,14 @@ impl Interval {
         self.min <= x && x <= self.max
     }
 
+    pub fn surrounds(&self, x: f32) -> bool {
+        self.min < x && x < self.max
+    }
+
+    pub fn expand(&self, delta: f32) -> Interval {
+        let padding = delta / 2.0;
+        Interval::new(self.min - padding, self.max + padding)
+    }
 
     pub fn clamp(x: f32, min: f32, max: f32) -> f32 {
         if x < min {
@@ -31,8 +58,4 @@ impl Interval {
         }
         x
     }
-
-    // Commenter ou supprimer si inutilisées
-    // const EMPTY : Interval = Interval { min: f32::INFINITY, max: f32::NEG_INFINITY };
-    // const UNIVERSE : Interval = Interval { min: f32::NEG_INFINITY, max: f32::INFINITY };
 }
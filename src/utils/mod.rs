@@ -1,3 +1,10 @@
 pub mod interval;
 pub mod camera;
-pub mod utils;
\ No newline at end of file
+#[allow(clippy::module_inception)]
+pub mod utils;
+pub mod aabb;
+pub mod image;
+pub mod aperture;
+pub mod noise;
+#[cfg(feature = "window")]
+pub mod preview;
\ No newline at end of file
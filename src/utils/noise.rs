@@ -0,0 +1,174 @@
+use crate::ray::vec3::Point3;
+
+// Bruit cohérent en 3D, valeurs dans [-1, 1] environ
+pub trait Noise: Send + Sync {
+    fn noise(&self, p: Point3) -> f32;
+
+    // Somme d'octaves (mouvement brownien fractionnaire), chaque octave deux
+    // fois plus fine et deux fois moins forte que la précédente
+    fn fbm(&self, p: Point3, octaves: u32) -> f32 {
+        let mut sum = 0.0;
+        let (mut frequency, mut amplitude) = (1.0, 0.5);
+        for _ in 0..octaves {
+            sum += amplitude * self.noise(p * frequency);
+            frequency *= 2.0;
+            amplitude *= 0.5;
+        }
+        sum
+    }
+
+    // Comme `fbm` mais sur la valeur absolue du bruit : des plis marqués, toujours positive
+    fn turbulence(&self, p: Point3, octaves: u32) -> f32 {
+        let mut sum = 0.0;
+        let (mut frequency, mut amplitude) = (1.0, 0.5);
+        for _ in 0..octaves {
+            sum += amplitude * self.noise(p * frequency).abs();
+            frequency *= 2.0;
+            amplitude *= 0.5;
+        }
+        sum
+    }
+}
+
+// Table de permutation doublée ; elle ne dépend que de la graine, pour que les
+// scènes soient identiques d'un rendu à l'autre
+fn permutation(seed: u32) -> [u8; 512] {
+    let mut table: [u8; 256] = std::array::from_fn(|i| i as u8);
+    // Mélange de Fisher-Yates avec un xorshift, indépendant du générateur du rendu
+    let mut state = seed.wrapping_mul(0x9E37_79B9) | 1;
+    for i in (1..256).rev() {
+        state ^= state << 13;
+        state ^= state >> 17;
+        state ^= state << 5;
+        table.swap(i, state as usize % (i + 1));
+    }
+    let mut perm = [0; 512];
+    for (i, value) in perm.iter_mut().enumerate() {
+        *value = table[i & 255];
+    }
+    perm
+}
+
+// Bruit de gradient de Perlin (version améliorée de 2002)
+#[derive(Clone)]
+pub struct Perlin {
+    perm: [u8; 512],
+}
+
+impl Perlin {
+    pub fn new(seed: u32) -> Self {
+        Perlin { perm: permutation(seed) }
+    }
+}
+
+impl Noise for Perlin {
+    fn noise(&self, p: Point3) -> f32 {
+        let (xf, yf, zf) = (p.x().floor(), p.y().floor(), p.z().floor());
+        let (x, y, z) = (p.x() - xf, p.y() - yf, p.z() - zf);
+        let (xi, yi, zi) = ((xf as i32 & 255) as usize, (yf as i32 & 255) as usize, (zf as i32 & 255) as usize);
+        let (u, v, w) = (fade(x), fade(y), fade(z));
+
+        let perm = &self.perm;
+        let a = perm[xi] as usize + yi;
+        let (aa, ab) = (perm[a] as usize + zi, perm[a + 1] as usize + zi);
+        let b = perm[xi + 1] as usize + yi;
+        let (ba, bb) = (perm[b] as usize + zi, perm[b + 1] as usize + zi);
+
+        lerp(w,
+            lerp(v,
+                lerp(u, grad(perm[aa], x, y, z), grad(perm[ba], x - 1.0, y, z)),
+                lerp(u, grad(perm[ab], x, y - 1.0, z), grad(perm[bb], x - 1.0, y - 1.0, z))),
+            lerp(v,
+                lerp(u, grad(perm[aa + 1], x, y, z - 1.0), grad(perm[ba + 1], x - 1.0, y, z - 1.0)),
+                lerp(u, grad(perm[ab + 1], x, y - 1.0, z - 1.0), grad(perm[bb + 1], x - 1.0, y - 1.0, z - 1.0))))
+    }
+}
+
+impl Default for Perlin {
+    fn default() -> Self {
+        Perlin::new(0)
+    }
+}
+
+// Bruit simplexe : somme des contributions des 4 sommets du tétraèdre
+// contenant le point, moins d'artefacts alignés sur les axes que Perlin
+#[derive(Clone)]
+pub struct Simplex {
+    perm: [u8; 512],
+}
+
+impl Simplex {
+    pub fn new(seed: u32) -> Self {
+        Simplex { perm: permutation(seed) }
+    }
+}
+
+impl Noise for Simplex {
+    fn noise(&self, p: Point3) -> f32 {
+        // Facteurs de déformation entre la grille cubique et la grille simplexe
+        const SKEW: f32 = 1.0 / 3.0;
+        const UNSKEW: f32 = 1.0 / 6.0;
+
+        let s = (p.x() + p.y() + p.z()) * SKEW;
+        let (i, j, k) = ((p.x() + s).floor(), (p.y() + s).floor(), (p.z() + s).floor());
+        let t = (i + j + k) * UNSKEW;
+        let x0 = [p.x() - (i - t), p.y() - (j - t), p.z() - (k - t)];
+
+        // Ordre des axes : le tétraèdre est choisi selon la plus grande coordonnée
+        let (o1, o2) = if x0[0] >= x0[1] {
+            if x0[1] >= x0[2] {
+                ([1, 0, 0], [1, 1, 0])
+            } else if x0[0] >= x0[2] {
+                ([1, 0, 0], [1, 0, 1])
+            } else {
+                ([0, 0, 1], [1, 0, 1])
+            }
+        } else if x0[1] < x0[2] {
+            ([0, 0, 1], [0, 1, 1])
+        } else if x0[0] < x0[2] {
+            ([0, 1, 0], [0, 1, 1])
+        } else {
+            ([0, 1, 0], [1, 1, 0])
+        };
+
+        let corners = [[0, 0, 0], o1, o2, [1, 1, 1]];
+        let (ii, jj, kk) = ((i as i32 & 255) as usize, (j as i32 & 255) as usize, (k as i32 & 255) as usize);
+        let perm = &self.perm;
+        let mut sum = 0.0;
+        for (n, corner) in corners.iter().enumerate() {
+            let offset = n as f32 * UNSKEW;
+            let x = x0[0] - corner[0] as f32 + offset;
+            let y = x0[1] - corner[1] as f32 + offset;
+            let z = x0[2] - corner[2] as f32 + offset;
+            let falloff = 0.6 - x * x - y * y - z * z;
+            if falloff > 0.0 {
+                let hash = perm[ii + corner[0] + perm[jj + corner[1] + perm[kk + corner[2]] as usize] as usize];
+                sum += falloff.powi(4) * grad(hash, x, y, z);
+            }
+        }
+        32.0 * sum
+    }
+}
+
+impl Default for Simplex {
+    fn default() -> Self {
+        Simplex::new(0)
+    }
+}
+
+// Raccord de degré 5 : dérivées première et seconde nulles aux bords des cellules
+fn fade(t: f32) -> f32 {
+    t * t * t * (t * (t * 6.0 - 15.0) + 10.0)
+}
+
+fn lerp(t: f32, a: f32, b: f32) -> f32 {
+    a + t * (b - a)
+}
+
+// Produit scalaire avec l'un des 12 gradients (milieux des arêtes d'un cube)
+fn grad(hash: u8, x: f32, y: f32, z: f32) -> f32 {
+    let h = hash & 15;
+    let u = if h < 8 { x } else { y };
+    let v = if h < 4 { y } else if h == 12 || h == 14 { x } else { z };
+    (if h & 1 == 0 { u } else { -u }) + (if h & 2 == 0 { v } else { -v })
+}
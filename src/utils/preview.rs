@@ -0,0 +1,49 @@
+use crate::utils::camera::{RenderObserver, Tile};
+use crate::utils::image::Image;
+use crate::ray::color::DisplayTransform;
+use minifb::{Window, WindowOptions, Key};
+
+// Fenêtre d'aperçu mise à jour à chaque tuile terminée
+pub struct Preview {
+    window: Window,
+    buffer: Vec<u32>,
+    width: usize,
+    height: usize,
+    display: DisplayTransform,
+}
+
+impl Preview {
+    pub fn new(width: usize, height: usize, display: DisplayTransform) -> Result<Self, minifb::Error> {
+        let mut window = Window::new(
+            "Ray Tracer Preview",
+            width,
+            height,
+            WindowOptions::default(),
+        )?;
+        window.limit_update_rate(Some(std::time::Duration::from_micros(16600)));
+
+        Ok(Preview { window, buffer: vec![0; width * height], width, height, display })
+    }
+
+    // Garde la fenêtre ouverte jusqu'à sa fermeture ou l'appui sur Échap
+    pub fn wait(&mut self) {
+        while self.window.is_open() && !self.window.is_key_down(Key::Escape) {
+            if self.window.update_with_buffer(&self.buffer, self.width, self.height).is_err() {
+                break;
+            }
+        }
+    }
+}
+
+impl RenderObserver for Preview {
+    fn tile_done(&mut self, image: &Image, tile: Tile) {
+        for y in tile.y0..tile.y1 {
+            for x in tile.x0..tile.x1 {
+                let [r, g, b] = self.display.to_rgb8(image.get(x, y)).map(u32::from);
+                self.buffer[y * self.width + x] = (r << 16) | (g << 8) | b;
+            }
+        }
+        // Une fenêtre fermée pendant le rendu ne doit pas interrompre celui-ci
+        let _ = self.window.update_with_buffer(&self.buffer, self.width, self.height);
+    }
+}
@@ -1,10 +1,20 @@
-use rand::Rng;
+use rand::{Rng, SeedableRng};
+use rand::rngs::StdRng;
+use std::cell::RefCell;
+
+thread_local! {
+    // Générateur propre à chaque thread, réinitialisable pour des rendus reproductibles
+    static RNG: RefCell<StdRng> = RefCell::new(StdRng::from_entropy());
+}
+
+pub fn seed_rng(seed: u64) {
+    RNG.with(|rng| *rng.borrow_mut() = StdRng::seed_from_u64(seed));
+}
 
 pub fn random_double() -> f32 {
-    let mut rng = rand::thread_rng();
-    rng.gen::<f32>()
+    RNG.with(|rng| rng.borrow_mut().gen::<f32>())
 }
 
 pub fn random_double_range(min: f32, max: f32) -> f32 {
-    rand::thread_rng().gen_range(min..max)
-}
\ No newline at end of file
+    RNG.with(|rng| rng.borrow_mut().gen_range(min..max))
+}